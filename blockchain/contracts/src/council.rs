@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use crate::state::{Council, PlatformConfig, Proposal, ProposalAction, ProposalStatus};
+use crate::events::{CouncilRotated, CouncilVetoed, PlatformPauseSet, ProposalExecuted, ProposalFastTracked};
+use crate::error::SoreinError;
+use crate::governance::check_ready_for_completion;
+
+/// Context for creating the council.
+#[derive(Accounts)]
+pub struct InitializeCouncil<'info> {
+    /// The platform administrator, who seats the first council.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// The platform configuration account holding the admin key.
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        has_one = admin @ SoreinError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The council account to be initialized.
+    #[account(
+        init,
+        payer = admin,
+        space = Council::SPACE,
+        seeds = [b"council"],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to seat the first council. Later membership changes go through RotateCouncil proposals.
+pub fn initialize_council(ctx: Context<InitializeCouncil>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let council = &mut ctx.accounts.council;
+    council.rotate(&members, threshold, clock.unix_timestamp)?;
+    council.bump = ctx.bumps.council;
+
+    emit!(CouncilRotated {
+        proposal_id: None,
+        members,
+        threshold,
+        term_end: council.term_end,
+    });
+
+    Ok(())
+}
+
+/// Context for the council vetoing or fast-tracking a queued proposal.
+#[derive(Accounts)]
+pub struct CouncilProposalAction<'info> {
+    /// The council member submitting the action; counts towards the threshold.
+    pub member: Signer<'info>,
+    /// The council whose threshold must be met.
+    #[account(seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    /// The queued proposal the council acts on.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Instruction for the council to veto a proposal while it waits out its timelock.
+/// The other approving members sign the transaction and are passed as remaining accounts.
+pub fn council_veto_proposal(ctx: Context<CouncilProposalAction>, proposal_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let approvers = check_council_approval(
+        &ctx.accounts.council,
+        &ctx.accounts.member,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    check_in_timelock(proposal, proposal_id, clock.unix_timestamp)?;
+    proposal.status = ProposalStatus::Vetoed;
+
+    emit!(CouncilVetoed {
+        proposal_id,
+        approvers,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Instruction for the council to skip the rest of a queued proposal's timelock so it can be executed now.
+/// The other approving members sign the transaction and are passed as remaining accounts.
+pub fn council_fast_track_proposal(ctx: Context<CouncilProposalAction>, proposal_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let approvers = check_council_approval(
+        &ctx.accounts.council,
+        &ctx.accounts.member,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    check_in_timelock(proposal, proposal_id, clock.unix_timestamp)?;
+    let previous_eta = proposal.eta;
+    proposal.eta = clock.unix_timestamp;
+
+    emit!(ProposalFastTracked {
+        proposal_id,
+        approvers,
+        previous_eta,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for the council pausing or resuming the platform.
+#[derive(Accounts)]
+pub struct CouncilSetPaused<'info> {
+    /// The council member submitting the action; counts towards the threshold.
+    pub member: Signer<'info>,
+    /// The council whose threshold must be met.
+    #[account(seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    /// The platform configuration account holding the pause flag.
+    #[account(mut, seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Instruction for the council to halt or resume staking operations in an emergency.
+/// The other approving members sign the transaction and are passed as remaining accounts.
+pub fn council_set_paused(ctx: Context<CouncilSetPaused>, paused: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let approvers = check_council_approval(
+        &ctx.accounts.council,
+        &ctx.accounts.member,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
+
+    ctx.accounts.platform_config.paused = paused;
+
+    emit!(PlatformPauseSet {
+        paused,
+        approvers,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for executing a queued RotateCouncil proposal.
+#[derive(Accounts)]
+pub struct ExecuteCouncilRotation<'info> {
+    /// The caller who executes the proposal (can be anyone since it's permissionless).
+    pub executor: Signer<'info>,
+    /// The platform configuration account holding the execution grace period.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal carrying the new council.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The council being rotated.
+    #[account(mut, seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
+}
+
+/// Instruction to seat the council named by a queued RotateCouncil proposal and start its term.
+pub fn execute_council_rotation(ctx: Context<ExecuteCouncilRotation>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_ready_for_completion(proposal, &ctx.accounts.platform_config)?;
+
+    let action = proposal.action.ok_or(SoreinError::ProposalNotExecutable)?;
+    let (members, member_count, threshold) = match action {
+        ProposalAction::RotateCouncil { members, member_count, threshold } => (members, member_count, threshold),
+        _ => return err!(SoreinError::ProposalNotExecutable),
+    };
+    let members = members.get(..member_count as usize).ok_or(SoreinError::InvalidProposalParameters)?;
+
+    let clock = Clock::get()?;
+    let council = &mut ctx.accounts.council;
+    council.rotate(members, threshold, clock.unix_timestamp)?;
+
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+
+    emit!(CouncilRotated {
+        proposal_id: Some(proposal_id),
+        members: members.to_vec(),
+        threshold,
+        term_end: council.term_end,
+    });
+    emit!(ProposalExecuted {
+        proposal_id,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+        action: Some(action),
+    });
+
+    Ok(())
+}
+
+/// Checks that the council's term is running and that the submitting member plus the signing
+/// members in `approvers` meet its threshold. Returns the distinct approving members.
+fn check_council_approval(
+    council: &Council,
+    member: &Signer,
+    approvers: &[AccountInfo],
+    now: i64,
+) -> Result<Vec<Pubkey>> {
+    if now > council.term_end {
+        return err!(SoreinError::CouncilTermExpired);
+    }
+    let mut approved: Vec<Pubkey> = Vec::with_capacity(approvers.len() + 1);
+    for (key, is_signer) in std::iter::once((member.key, true))
+        .chain(approvers.iter().map(|account_info| (account_info.key, account_info.is_signer)))
+    {
+        if !is_signer || !council.is_member(key) {
+            return err!(SoreinError::UnauthorizedUser);
+        }
+        if !approved.contains(key) {
+            approved.push(*key);
+        }
+    }
+    if approved.len() < council.threshold as usize {
+        return err!(SoreinError::InsufficientCouncilApprovals);
+    }
+    Ok(approved)
+}
+
+/// Checks that a proposal is queued and its timelock has not yet elapsed.
+fn check_in_timelock(proposal: &Proposal, proposal_id: u64, now: i64) -> Result<()> {
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if proposal.status != ProposalStatus::Queued || now >= proposal.eta {
+        return err!(SoreinError::ProposalNotExecutable);
+    }
+    Ok(())
+}
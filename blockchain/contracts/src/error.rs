@@ -20,6 +20,10 @@ pub enum SoreinError {
     #[msg("Unauthorized: Caller does not have required permissions.")]
     UnauthorizedUser = 103,
 
+    /// Error when the platform is paused by the council.
+    #[msg("Platform is paused by the council.")]
+    PlatformPaused = 104,
+
     /// Error when an AI agent is already registered with the given ID.
     #[msg("AI agent is already registered with this ID.")]
     AgentAlreadyRegistered = 200,
@@ -32,6 +36,50 @@ pub enum SoreinError {
     #[msg("Invalid AI agent metadata provided.")]
     InvalidAgentMetadata = 202,
 
+    /// Error when a performance score comes from neither the configured score feed nor a registered evaluator.
+    #[msg("Score report is not from an authorized score feed or evaluator.")]
+    UnauthorizedScoreSource = 203,
+
+    /// Error when a score report is malformed, for another agent, or out of range.
+    #[msg("Invalid performance score report.")]
+    InvalidScoreReport = 204,
+
+    /// Error when a score report is not newer than the last accepted one.
+    #[msg("Score report is not newer than the last accepted report.")]
+    StaleScoreReport = 205,
+
+    /// Error when the ed25519 attestation instruction is missing or malformed.
+    #[msg("Missing or invalid ed25519 score attestation.")]
+    InvalidAttestation = 206,
+
+    /// Error when staking on an agent that is not active.
+    #[msg("Agent is not accepting new stakes.")]
+    AgentNotActive = 207,
+
+    /// Error when an agent is not in the status an action requires.
+    #[msg("Agent is not in the required status for this action.")]
+    InvalidAgentStatus = 208,
+
+    /// Error when closing an agent that still has open stake positions.
+    #[msg("Agent still has open stake positions.")]
+    AgentHasOpenPositions = 209,
+
+    /// Error when transferring an agent to its current owner.
+    #[msg("New owner must differ from the current owner.")]
+    InvalidNewOwner = 210,
+
+    /// Error when an action needs an agent that is not tokenized.
+    #[msg("Agent is already tokenized; ownership follows its NFT.")]
+    AgentTokenized = 211,
+
+    /// Error when an action needs a tokenized agent.
+    #[msg("Agent has not been tokenized.")]
+    AgentNotTokenized = 212,
+
+    /// Error when registering an agent beyond the per-owner limit.
+    #[msg("Owner has reached the maximum number of agents.")]
+    TooManyAgentsOwned = 213,
+
     /// Error when the stake amount is zero or negative.
     #[msg("Stake amount must be greater than zero.")]
     InvalidStakeAmount = 300,
@@ -64,6 +112,26 @@ pub enum SoreinError {
     #[msg("Unstake amount exceeds staked balance.")]
     InvalidUnstakeAmount = 307,
 
+    /// Error when no pending withdrawal has completed its cooldown.
+    #[msg("No withdrawals have completed their cooldown.")]
+    NoUnlockedWithdrawals = 308,
+
+    /// Error when a vault account does not match the platform vault.
+    #[msg("Vault account does not match the platform vault.")]
+    InvalidVault = 309,
+
+    /// Error when unstaking before the stake lockup ends.
+    #[msg("Stake is locked until its lockup period ends.")]
+    StakeLocked = 310,
+
+    /// Error when closing a stake position that still holds stake or unclaimed rewards.
+    #[msg("Stake position still holds stake or unclaimed rewards.")]
+    PositionNotEmpty = 311,
+
+    /// Error when the pending withdrawal queue is full.
+    #[msg("Too many pending withdrawals queued.")]
+    TooManyPendingWithdrawals = 312,
+
     /// Error when the provided vote weight or option is invalid.
     #[msg("Invalid vote weight or option provided.")]
     InvalidVote = 400,
@@ -84,6 +152,202 @@ pub enum SoreinError {
     #[msg("Governance action is not allowed at this time.")]
     GovernanceActionNotAllowed = 404,
 
+    /// Error when governance is disabled in the platform configuration.
+    #[msg("Governance is not enabled.")]
+    GovernanceDisabled = 405,
+
+    /// Error when a vote is cast outside the proposal's voting window.
+    #[msg("Proposal is not open for voting.")]
+    ProposalNotActive = 406,
+
+    /// Error when the selected vote option does not exist on the proposal.
+    #[msg("Vote option does not exist on this proposal.")]
+    InvalidVoteOption = 407,
+
+    /// Error when a proposal has too few or too many options, or an option label is too long.
+    #[msg("Proposal must have between 2 and 10 valid options.")]
+    InvalidVoteOptions = 408,
+
+    /// Error when finalizing a proposal that has already been finalized.
+    #[msg("Proposal has already been finalized.")]
+    ProposalAlreadyFinalized = 409,
+
+    /// Error when finalizing a proposal before its voting period has ended.
+    #[msg("Voting period has not ended yet.")]
+    VotingPeriodNotEnded = 410,
+
+    /// Error when the voter has no lockup-weighted voting power.
+    #[msg("Insufficient voting power.")]
+    InsufficientVotingPower = 411,
+
+    /// Error when executing a proposal that was not approved with the approve option or has no action.
+    #[msg("Proposal is not approved for execution.")]
+    ProposalNotExecutable = 412,
+
+    /// Error when executing a proposal that has already been executed.
+    #[msg("Proposal has already been executed.")]
+    ProposalAlreadyExecuted = 413,
+
+    /// Error when executing a queued proposal before its timelock has elapsed.
+    #[msg("Proposal timelock has not elapsed yet.")]
+    TimelockNotElapsed = 414,
+
+    /// Error when executing a queued proposal after its grace period has ended.
+    #[msg("Proposal execution window has expired.")]
+    ExecutionWindowExpired = 415,
+
+    /// Error when cancelling a proposal that is no longer active or queued.
+    #[msg("Proposal can no longer be cancelled.")]
+    ProposalNotCancellable = 416,
+
+    /// Error when a proposal is edited after it has left draft or been signed off.
+    #[msg("Proposal can no longer be edited.")]
+    ProposalNotEditable = 417,
+
+    /// Error when an instruction is attached out of order or beyond the per-proposal limit.
+    #[msg("Invalid proposal instruction index.")]
+    InvalidInstructionIndex = 418,
+
+    /// Error when an instruction has too many accounts or too much data.
+    #[msg("Proposal instruction exceeds the maximum size.")]
+    InstructionTooLarge = 419,
+
+    /// Error when invoking an instruction that has already been executed.
+    #[msg("Proposal instruction has already been executed.")]
+    InstructionAlreadyExecuted = 420,
+
+    /// Error when completing a proposal before all of its instructions have been executed.
+    #[msg("Not all proposal instructions have been executed.")]
+    InstructionsNotExecuted = 421,
+
+    /// Error when a treasury spend is zero or exceeds the per-proposal share of the vault.
+    #[msg("Treasury spend exceeds the per-proposal limit.")]
+    TreasurySpendTooLarge = 422,
+
+    /// Error when a user who has delegated their voting power tries to vote directly.
+    #[msg("Voting power is delegated; undelegate to vote directly.")]
+    VotingPowerDelegated = 423,
+
+    /// Error when a delegation changed after the proposal's voting started.
+    #[msg("Delegation changed after voting started on this proposal.")]
+    DelegationChangedDuringVote = 424,
+
+    /// Error when delegating to oneself, re-delegating received power or undelegating without a delegate.
+    #[msg("Invalid delegate.")]
+    InvalidDelegate = 425,
+
+    /// Error when delegating while a delegation is already active.
+    #[msg("Voting power is already delegated.")]
+    AlreadyDelegated = 426,
+
+    /// Error when the delegate already has the maximum number of delegators.
+    #[msg("Delegate has reached the maximum number of delegators.")]
+    TooManyDelegators = 427,
+
+    /// Error when a delegator stake account passed at vote time is invalid, duplicated or not delegated to the voter.
+    #[msg("Invalid delegator stake account.")]
+    InvalidDelegatorAccount = 428,
+
+    /// Error when the creator's stake is below the minimum required to propose.
+    #[msg("Insufficient stake to create a proposal.")]
+    InsufficientStakeToPropose = 429,
+
+    /// Error when a proposal deposit has already been refunded or slashed.
+    #[msg("Proposal deposit has already been settled.")]
+    DepositAlreadySettled = 430,
+
+    /// Error when settling the deposit of a proposal that is still being voted on.
+    #[msg("Proposal has not been finalized yet.")]
+    ProposalNotFinalized = 431,
+
+    /// Error when fewer council members signed than the council threshold requires.
+    #[msg("Not enough council members approved this action.")]
+    InsufficientCouncilApprovals = 432,
+
+    /// Error when the council acts after its term has ended.
+    #[msg("Council term has expired.")]
+    CouncilTermExpired = 433,
+
+    /// Error when adding more co-signatories than a proposal allows.
+    #[msg("Proposal has reached the maximum number of signatories.")]
+    TooManySignatories = 434,
+
+    /// Error when signing off a proposal before every co-signatory has.
+    #[msg("Not all signatories have signed off the proposal.")]
+    SignatoriesPending = 435,
+
+    /// Error when a co-signatory signs off a proposal twice.
+    #[msg("Signatory has already signed off the proposal.")]
+    AlreadySignedOff = 436,
+
+    /// Error when acting on a proposal that has expired.
+    #[msg("Proposal has expired.")]
+    ProposalExpired = 437,
+
+    /// Error when closing a proposal or vote record whose proposal is not final yet.
+    #[msg("Proposal cannot be closed yet.")]
+    ProposalNotClosable = 438,
+
+    /// Error when claiming a voting reward before its epoch has ended.
+    #[msg("Governance reward epoch has not ended yet.")]
+    RewardEpochNotEnded = 439,
+
+    /// Error when a governance reward has already been claimed.
+    #[msg("Reward has already been claimed.")]
+    RewardAlreadyClaimed = 440,
+
+    /// Error when claiming a proposer reward for a proposal that did not pass.
+    #[msg("Proposal did not pass.")]
+    ProposalNotPassed = 441,
+
+    /// Error when a proposal's voting duration is outside the configured bounds.
+    #[msg("Voting duration is outside the allowed range.")]
+    InvalidVotingDuration = 442,
+
+    /// Error when delegating less than the minimum stake.
+    #[msg("Stake is below the minimum required to delegate.")]
+    InsufficientDelegatedStake = 443,
+
+    /// Error when claiming a voting reward on a proposal that is unfinished, spam or cancelled by the guardian.
+    #[msg("Proposal is not eligible for governance rewards.")]
+    ProposalNotRewardEligible = 444,
+
+    /// Error when the governance reward vault can't cover a reward.
+    #[msg("Insufficient funds in the governance reward vault.")]
+    InsufficientGovernanceRewardFunds = 445,
+
+    /// Error when a proposer reward would exceed its epoch's cap.
+    #[msg("Proposer reward cap for the epoch has been reached.")]
+    ProposerRewardCapReached = 446,
+
+    /// Error when an admin setter is called while governance controls the setting.
+    #[msg("Setting is controlled by governance proposals.")]
+    GovernanceControlled = 447,
+
+    /// Error when a proposal action has invalid parameters.
+    #[msg("Invalid proposal action parameters.")]
+    InvalidProposalAction = 448,
+
+    /// Error when executing an action that has its own execute instruction.
+    #[msg("Action must be executed through its dedicated instruction.")]
+    UnsupportedProposalAction = 449,
+
+    /// Error when a treasury spend exceeds its bucket's budget for the epoch.
+    #[msg("Treasury bucket budget exceeded for this epoch.")]
+    TreasuryBudgetExceeded = 450,
+
+    /// Error when the council members or threshold are invalid.
+    #[msg("Invalid council members or threshold.")]
+    InvalidCouncil = 451,
+
+    /// Error when a ranking is not a valid ranked-choice ballot.
+    #[msg("Ranking is not a valid ranked-choice ballot.")]
+    InvalidBallot = 452,
+
+    /// Error when relinquishing an open vote that sets the voter's weight in the reward epoch.
+    #[msg("Vote sets the voter's reward weight and cannot be relinquished while voting is open.")]
+    VoteHoldsRewardWeight = 453,
+
     /// Error when the platform configuration parameters are invalid.
     #[msg("Invalid platform configuration parameters.")]
     InvalidConfig = 500,
@@ -96,6 +360,26 @@ pub enum SoreinError {
     #[msg("Metadata size exceeds the maximum allowed limit.")]
     MetadataTooLarge = 502,
 
+    /// Error when the unstake cooldown is invalid.
+    #[msg("Invalid unstake cooldown.")]
+    InvalidCooldown = 503,
+
+    /// Error when a lockup period is outside the configured bounds.
+    #[msg("Invalid lockup period.")]
+    InvalidLockupPeriod = 504,
+
+    /// Error when the lockup multiplier or tiers are invalid.
+    #[msg("Invalid lockup multiplier.")]
+    InvalidLockupMultiplier = 505,
+
+    /// Error when a governance threshold is outside 0 to 10000 basis points.
+    #[msg("Governance thresholds must be between 0 and 10000 basis points.")]
+    InvalidThreshold = 506,
+
+    /// Error when the timelock configuration is invalid.
+    #[msg("Invalid timelock configuration.")]
+    InvalidTimelock = 507,
+
     /// Error when an arithmetic operation overflows or underflows.
     #[msg("Arithmetic overflow or underflow occurred.")]
     ArithmeticError = 600,
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{AgentStatus, ProposalAction, ProposalStatus, TreasuryBucket};
 
 /// Event definitions for on-chain logging and tracking of mycela AI platform activities.
 /// These events are emitted during key contract operations to provide transparency and enable off-chain tracking.
@@ -40,7 +41,7 @@ pub struct AgentRegistered {
 pub struct AgentUpdated {
     /// The unique ID of the AI agent.
     pub agent_id: u64,
-    /// The owner of the AI agent.
+    /// The owner (or NFT holder) who updated the agent.
     pub owner: Pubkey,
     /// The timestamp when the agent was updated.
     pub timestamp: i64,
@@ -48,6 +49,84 @@ pub struct AgentUpdated {
     pub new_metadata: String,
 }
 
+#[event]
+pub struct AgentStatusChanged {
+    /// The unique ID of the AI agent.
+    pub agent_id: u64,
+    /// The owner (or NFT holder) who changed the status.
+    pub owner: Pubkey,
+    /// The status the agent left.
+    pub previous_status: AgentStatus,
+    /// The agent's new status (paused, resumed to active, or retired).
+    pub status: AgentStatus,
+    /// The timestamp when the status changed.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentOwnerProposed {
+    /// The unique ID of the AI agent.
+    pub agent_id: u64,
+    /// The current owner proposing the transfer.
+    pub owner: Pubkey,
+    /// The wallet that must accept ownership (default if the proposal was withdrawn).
+    pub pending_owner: Pubkey,
+    /// The timestamp when the transfer was proposed.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentOwnerTransferred {
+    /// The unique ID of the AI agent.
+    pub agent_id: u64,
+    /// The previous owner of the AI agent.
+    pub previous_owner: Pubkey,
+    /// The new owner who accepted the transfer, or the NFT holder it was synced to.
+    pub new_owner: Pubkey,
+    /// The timestamp when the transfer took effect.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentTokenized {
+    /// The unique ID of the AI agent.
+    pub agent_id: u64,
+    /// The owner who received the NFT.
+    pub owner: Pubkey,
+    /// The mint of the agent's 1/1 NFT.
+    pub nft_mint: Pubkey,
+    /// The metadata URI of the NFT.
+    pub uri: String,
+    /// The timestamp when the NFT was minted.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceScoreUpdated {
+    /// The unique ID of the AI agent.
+    pub agent_id: u64,
+    /// The reported score (in basis points).
+    pub score: u64,
+    /// The agent's moving-average performance score after this report.
+    pub ema_score: u64,
+    /// The score feed account or evaluator key that reported the score.
+    pub source: Pubkey,
+    /// The timestamp the evaluation was run at.
+    pub observed_at: i64,
+    /// The timestamp when the score was recorded.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentClosed {
+    /// The unique ID of the retired AI agent.
+    pub agent_id: u64,
+    /// The owner (or NFT holder) who closed the agent and received the account's rent.
+    pub owner: Pubkey,
+    /// The timestamp when the agent was closed.
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct StakeDeposited {
     /// The user who deposited the stake.
@@ -108,8 +187,8 @@ pub struct VoteCast {
     pub voter: Pubkey,
     /// The timestamp when the vote was cast.
     pub timestamp: i64,
-    /// The option chosen (e.g., 0 for No, 1 for Yes).
-    pub vote_option: u8,
+    /// The options chosen (e.g., [0] for No, [1] for Yes), most preferred first for ranked-choice votes.
+    pub choices: Vec<u8>,
     /// The weight of the vote (based on staked amount or other criteria).
     pub vote_weight: u64,
 }
@@ -120,12 +199,210 @@ pub struct ProposalFinalized {
     pub proposal_id: u64,
     /// The timestamp when the proposal was finalized.
     pub timestamp: i64,
-    /// The result of the proposal (e.g., 0 for Rejected, 1 for Approved).
-    pub result: u8,
+    /// The final status of the proposal (Approved, Defeated, Vetoed or QuorumNotMet).
+    pub result: ProposalStatus,
     /// Total votes for each option (serialized as a string for simplicity).
     pub vote_summary: String,
 }
 
+#[event]
+pub struct ProposalQueued {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The timestamp when the proposal was queued.
+    pub timestamp: i64,
+    /// The earliest timestamp at which the proposal can be executed.
+    pub eta: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The account that triggered the execution.
+    pub executor: Pubkey,
+    /// The timestamp when the proposal was executed.
+    pub timestamp: i64,
+    /// The platform change that was applied, if the proposal carried one.
+    pub action: Option<ProposalAction>,
+}
+
+#[event]
+pub struct ProposalInstructionExecuted {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The position of the instruction within the proposal.
+    pub index: u16,
+    /// The program that was invoked.
+    pub program_id: Pubkey,
+    /// The timestamp when the instruction was executed.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The account that cancelled the proposal.
+    pub cancelled_by: Pubkey,
+    /// The timestamp when the proposal was cancelled.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalSignedOff {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The timestamp when voting opened.
+    pub start_time: i64,
+    /// The timestamp when voting closes.
+    pub end_time: i64,
+}
+
+#[event]
+pub struct ProposalFlaggedSpam {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The guardian that flagged the proposal.
+    pub flagged_by: Pubkey,
+    /// The timestamp when the proposal was flagged.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalDepositSettled {
+    /// The unique ID of the governance proposal.
+    pub proposal_id: u64,
+    /// The creator who posted the deposit.
+    pub creator: Pubkey,
+    /// The deposit amount refunded or slashed.
+    pub amount: u64,
+    /// Whether the deposit was refunded to the creator (false if slashed to the treasury).
+    pub refunded: bool,
+    /// The timestamp when the deposit was settled.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilVetoed {
+    /// The unique ID of the vetoed proposal.
+    pub proposal_id: u64,
+    /// The council members who approved the veto.
+    pub approvers: Vec<Pubkey>,
+    /// The timestamp of the veto.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalFastTracked {
+    /// The unique ID of the fast-tracked proposal.
+    pub proposal_id: u64,
+    /// The council members who approved the fast-track.
+    pub approvers: Vec<Pubkey>,
+    /// The eta the proposal had before it was fast-tracked.
+    pub previous_eta: i64,
+    /// The timestamp of the fast-track, which is the new eta.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformPauseSet {
+    /// Whether the platform is now paused.
+    pub paused: bool,
+    /// The council members who approved the change.
+    pub approvers: Vec<Pubkey>,
+    /// The timestamp of the change.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilRotated {
+    /// The proposal that rotated the council (None when the council was first initialized).
+    pub proposal_id: Option<u64>,
+    /// The new council members.
+    pub members: Vec<Pubkey>,
+    /// The number of member signatures the new council requires.
+    pub threshold: u8,
+    /// The end of the new council's term.
+    pub term_end: i64,
+}
+
+#[event]
+pub struct VotingRewardClaimed {
+    /// The voter who claimed the reward.
+    pub voter: Pubkey,
+    /// The proposal whose vote record was presented for the claim.
+    pub proposal: Pubkey,
+    /// The governance reward epoch the vote counted towards.
+    pub epoch: u64,
+    /// The reward paid.
+    pub amount: u64,
+    /// The timestamp of the claim.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposerRewardClaimed {
+    /// The unique ID of the passed proposal.
+    pub proposal_id: u64,
+    /// The creator who claimed the reward.
+    pub creator: Pubkey,
+    /// The reward paid.
+    pub amount: u64,
+    /// The timestamp of the claim.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryBudgetSet {
+    /// The proposal that set the budget.
+    pub proposal_id: u64,
+    /// The treasury mint whose budget changed.
+    pub mint: Pubkey,
+    /// The allocation bucket whose cap changed.
+    pub bucket: TreasuryBucket,
+    /// The new per-epoch spending cap.
+    pub epoch_cap: u64,
+    /// The timestamp when the budget was set.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasurySpent {
+    /// The proposal that authorized the spend.
+    pub proposal_id: u64,
+    /// The treasury mint that was paid out.
+    pub mint: Pubkey,
+    /// The allocation bucket the spend was charged against.
+    pub bucket: TreasuryBucket,
+    /// The recipient token account.
+    pub recipient: Pubkey,
+    /// The amount paid out (in token units).
+    pub amount: u64,
+    /// The timestamp when the payout occurred.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesDelegated {
+    /// The user who delegated their voting power.
+    pub delegator: Pubkey,
+    /// The user receiving the voting power.
+    pub delegate: Pubkey,
+    /// The timestamp when the delegation was made.
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesUndelegated {
+    /// The user who revoked their delegation.
+    pub delegator: Pubkey,
+    /// The user who previously received the voting power.
+    pub delegate: Pubkey,
+    /// The timestamp when the delegation was revoked.
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RewardDistributed {
     /// The authority or system account that triggered the distribution.
@@ -1,7 +1,22 @@
 use anchor_lang::prelude::*;
-use crate::state::{PlatformConfig, Proposal, UserStake};
-use crate::events::{ProposalCreated, VoteCast, ProposalFinalized};
-use crate::error::HalnetError;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    BallotBox, GovernanceRewardEpoch, VoterRewardEpoch, SignatoryRecord, MAX_PROPOSAL_SIGNATORIES, PlatformConfig, Treasury, Proposal, ProposalAccountMeta, ProposalAction, ProposalContent, ProposalInstruction, ProposalStatus,
+    ProposalType, StakeCheckpoints, UserStake, VoteRecord, APPROVE_OPTION_INDEX, MAX_DELEGATORS, MAX_INSTRUCTION_ACCOUNTS,
+    MAX_INSTRUCTION_DATA_LENGTH, MAX_OPTION_LENGTH, MAX_PROPOSAL_DESCRIPTION_LENGTH,
+    MAX_PROPOSAL_INSTRUCTIONS, MAX_PROPOSAL_OPTIONS, MAX_PROPOSAL_TITLE_LENGTH, VETO_VOTE_OPTION, VoteType,
+};
+use crate::events::{
+    ProposalCancelled, ProposalCreated, ProposalDepositSettled, ProposalExecuted, ProposalFinalized,
+    ProposalFlaggedSpam, ProposalInstructionExecuted, ProposalQueued, ProposalSignedOff, VoteCast, VotesDelegated, VotesUndelegated,
+};
+
+/// Seed of the PDA that signs instructions executed by governance proposals.
+pub const GOVERNANCE_AUTHORITY_SEED: &[u8] = b"governance-authority";
+use crate::error::SoreinError;
+use crate::math;
 
 /// Context for creating a new governance proposal.
 #[derive(Accounts)]
@@ -10,7 +25,7 @@ pub struct CreateProposal<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
     /// The platform configuration account to ensure governance is enabled.
-    #[account(has_one = authority @ HalnetError::UnauthorizedAccess)]
+    #[account(mut, seeds = [b"platform-config"], bump = platform_config.bump)]
     pub platform_config: Account<'info, PlatformConfig>,
     /// The proposal account to be initialized.
     #[account(
@@ -21,6 +36,49 @@ pub struct CreateProposal<'info> {
         bump
     )]
     pub proposal: Account<'info, Proposal>,
+    /// The creator's stake account, checked against the minimum stake to propose.
+    #[account(
+        seeds = [b"user-stake", creator.key().as_ref()],
+        bump = creator_stake.bump,
+        constraint = creator_stake.user == creator.key() @ SoreinError::UnauthorizedUser
+    )]
+    pub creator_stake: Account<'info, UserStake>,
+    /// The stake mint the deposit is paid in.
+    #[account(
+        address = platform_config.stake_mint @ SoreinError::InvalidAccount,
+        mint::token_program = token_program
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    /// The creator's token account the deposit is drawn from.
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = creator,
+        token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The escrow holding the deposit until it is settled, owned by the proposal.
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"proposal-deposit", proposal.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = proposal,
+        token::token_program = token_program
+    )]
+    pub deposit_escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The ballot box for a ranked-choice proposal; omitted for other vote types.
+    #[account(
+        init,
+        payer = creator,
+        space = BallotBox::SPACE,
+        seeds = [b"ballot-box", proposal.key().as_ref()],
+        bump
+    )]
+    pub ballot_box: Option<Account<'info, BallotBox>>,
+    /// The token program owning the stake mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
     /// The system program for account initialization.
     pub system_program: Program<'info, System>,
 }
@@ -30,31 +88,58 @@ impl<'info> CreateProposal<'info> {
     pub fn validate(&self) -> Result<()> {
         // Check if governance is enabled in platform config.
         if !self.platform_config.governance_enabled {
-            return err!(FabeonError::GovernanceDisabled);
+            return err!(SoreinError::GovernanceDisabled);
+        }
+        // Only stakers above the configured minimum can propose.
+        if self.creator_stake.staked_amount < self.platform_config.min_stake_to_propose {
+            return err!(SoreinError::InsufficientStakeToPropose);
+        }
+        Ok(())
+    }
+
+    /// Validates that the requested voting period is within the configured bounds.
+    pub fn validate_voting_duration(&self, voting_duration: u64) -> Result<()> {
+        if !self.platform_config.is_valid_voting_duration(voting_duration) {
+            return err!(SoreinError::InvalidVotingDuration);
         }
-        // Placeholder for stake check (assumes a separate stake account or logic).
-        // In a real implementation, check if creator has staked tokens.
         Ok(())
     }
 }
 
-/// Instruction to create a new governance proposal.
+/// Instruction to create a new governance proposal as a draft. Voting opens once the creator
+/// signs it off; the configured deposit is escrowed from the creator until settle_proposal_deposit.
 pub fn create_proposal(
     ctx: Context<CreateProposal>,
-    title: String,
-    description: String,
+    content: ProposalContent,
+    proposal_type: ProposalType,
+    vote_type: VoteType,
     voting_duration: u64,
-    options: Vec<String>,
+    action: Option<ProposalAction>,
 ) -> Result<()> {
+    let ProposalContent { title, description, options } = content;
+
     // Validate the input and context.
     ctx.accounts.validate()?;
+    ctx.accounts.validate_voting_duration(voting_duration)?;
 
-    // Ensure the title and description are within size limits.
-    if title.len() > 100 || description.len() > 1000 {
-        return err!(RexoulError::InvalidInput);
+    check_proposal_content(&title, &description, &options, vote_type)?;
+    // Ranked-choice proposals keep the weight of every ranking for the runoff, so they need a ballot box.
+    if (vote_type == VoteType::RankedChoice) != ctx.accounts.ballot_box.is_some() {
+        return err!(SoreinError::InvalidAccount);
     }
-    if options.len() < 2 || options.len() > 10 {
-        return err!(RexoulError::InvalidVoteOptions);
+
+    // Escrow the deposit, crediting only what arrives (Token-2022 transfer fees are withheld in transit).
+    let deposit = ctx.accounts.platform_config.proposal_deposit;
+    if deposit > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.creator_token_account.to_account_info(),
+            mint: ctx.accounts.stake_mint.to_account_info(),
+            to: ctx.accounts.deposit_escrow.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, deposit, ctx.accounts.stake_mint.decimals)?;
+        ctx.accounts.deposit_escrow.reload()?;
     }
 
     let clock = Clock::get()?;
@@ -66,12 +151,42 @@ pub fn create_proposal(
     proposal.creator = ctx.accounts.creator.key();
     proposal.title = title.clone();
     proposal.description = description;
+    proposal.proposal_type = proposal_type;
+    proposal.vote_type = vote_type;
     proposal.options = options.clone();
     proposal.votes = vec![0; options.len()];
-    proposal.start_time = clock.unix_timestamp;
-    proposal.end_time = clock.unix_timestamp + voting_duration as i64;
-    proposal.status = 0; // 0 = Active
-    proposal.bump = *ctx.bumps.get("proposal").unwrap();
+    proposal.veto_votes = 0;
+    // Snapshot the thresholds so later config changes don't move the goalposts.
+    proposal.thresholds = platform_config.thresholds_for(proposal_type);
+    // The quorum denominator, voting power snapshot and voting period are set at sign-off.
+    proposal.total_voting_supply = 0;
+    proposal.quorum_votes = 0;
+    proposal.snapshot_slot = 0;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.voting_duration = voting_duration;
+    proposal.signatory_count = 0;
+    proposal.signed_off_count = 0;
+    proposal.start_time = 0;
+    proposal.end_time = 0;
+    proposal.status = ProposalStatus::Draft;
+    proposal.winning_option = None;
+    // The action only runs if option 0 (APPROVE_OPTION_INDEX) wins.
+    proposal.action = action;
+    proposal.instruction_count = 0;
+    proposal.executed_instruction_count = 0;
+    proposal.eta = 0;
+    proposal.executed_at = 0;
+    proposal.voter_count = 0;
+    proposal.deposit_amount = ctx.accounts.deposit_escrow.amount;
+    proposal.deposit_settled = false;
+    proposal.flagged_spam = false;
+    proposal.cancelled_by_guardian = false;
+    proposal.proposer_reward = 0;
+    proposal.proposer_reward_claimed = false;
+    proposal.bump = ctx.bumps.proposal;
+    if let Some(ballot_box) = ctx.accounts.ballot_box.as_mut() {
+        ballot_box.init(proposal.key(), ctx.bumps.ballot_box);
+    }
 
     // Increment the proposal counter in platform config.
     platform_config.proposal_count += 1;
@@ -88,6 +203,301 @@ pub fn create_proposal(
     Ok(())
 }
 
+/// Checks a proposal's title, description and options against the size limits and its vote type.
+fn check_proposal_content(title: &str, description: &str, options: &[String], vote_type: VoteType) -> Result<()> {
+    // Ensure the title and description are within size limits.
+    if title.len() > MAX_PROPOSAL_TITLE_LENGTH || description.len() > MAX_PROPOSAL_DESCRIPTION_LENGTH {
+        return err!(SoreinError::InvalidProposalParameters);
+    }
+    if options.len() < 2 || options.len() > MAX_PROPOSAL_OPTIONS {
+        return err!(SoreinError::InvalidVoteOptions);
+    }
+    if options.iter().any(|option| option.len() > MAX_OPTION_LENGTH) {
+        return err!(SoreinError::InvalidVoteOptions);
+    }
+    if !vote_type.is_valid_for(options.len()) {
+        return err!(SoreinError::InvalidVoteOptions);
+    }
+    Ok(())
+}
+
+/// Context for editing a draft proposal.
+#[derive(Accounts)]
+pub struct UpdateDraftProposal<'info> {
+    /// The proposal creator, the only account allowed to edit the draft.
+    pub creator: Signer<'info>,
+    /// The draft proposal to edit.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Instruction to replace a draft's title, description and options. The vote type and action are fixed at creation.
+pub fn update_draft_proposal(
+    ctx: Context<UpdateDraftProposal>,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    options: Vec<String>,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_proposal_editable(proposal)?;
+    check_proposal_content(&title, &description, &options, proposal.vote_type)?;
+
+    proposal.title = title;
+    proposal.description = description;
+    proposal.votes = vec![0; options.len()];
+    proposal.options = options;
+
+    msg!("Draft proposal {} updated", proposal_id);
+    Ok(())
+}
+
+/// Context for adding a co-signatory to a draft proposal.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, signatory: Pubkey)]
+pub struct AddSignatory<'info> {
+    /// The proposal creator, who pays for the signatory record.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// The draft proposal the signatory is added to.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// The signatory record to be initialized.
+    #[account(
+        init,
+        payer = creator,
+        space = SignatoryRecord::SPACE,
+        seeds = [b"signatory", proposal.key().as_ref(), signatory.as_ref()],
+        bump
+    )]
+    pub signatory_record: Account<'info, SignatoryRecord>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to require another account's sign-off before the draft can open for voting.
+pub fn add_signatory(ctx: Context<AddSignatory>, proposal_id: u64, signatory: Pubkey) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_proposal_editable(proposal)?;
+    if proposal.signatory_count >= MAX_PROPOSAL_SIGNATORIES {
+        return err!(SoreinError::TooManySignatories);
+    }
+
+    let signatory_record = &mut ctx.accounts.signatory_record;
+    signatory_record.proposal = proposal.key();
+    signatory_record.creator = ctx.accounts.creator.key();
+    signatory_record.signatory = signatory;
+    signatory_record.signed_off = false;
+    signatory_record.bump = ctx.bumps.signatory_record;
+    proposal.signatory_count += 1;
+
+    msg!("Signatory {} added to proposal {}", signatory, proposal_id);
+    Ok(())
+}
+
+/// Context for removing a co-signatory from a draft proposal.
+#[derive(Accounts)]
+pub struct RemoveSignatory<'info> {
+    /// The proposal creator, refunded the signatory record rent.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// The draft proposal the signatory is removed from.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// The signatory record, closed once removed.
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"signatory", proposal.key().as_ref(), signatory_record.signatory.as_ref()],
+        bump = signatory_record.bump
+    )]
+    pub signatory_record: Account<'info, SignatoryRecord>,
+}
+
+/// Instruction to drop a co-signatory from a draft proposal.
+pub fn remove_signatory(ctx: Context<RemoveSignatory>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let signatory_record = &ctx.accounts.signatory_record;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_proposal_editable(proposal)?;
+
+    proposal.signatory_count -= 1;
+    if signatory_record.signed_off {
+        proposal.signed_off_count -= 1;
+    }
+
+    msg!("Signatory {} removed from proposal {}", signatory_record.signatory, proposal_id);
+    Ok(())
+}
+
+/// Context for a co-signatory signing off a draft proposal.
+#[derive(Accounts)]
+pub struct SignOffSignatory<'info> {
+    /// The co-signatory signing off.
+    pub signatory: Signer<'info>,
+    /// The draft proposal being signed off.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The signatory's record for this proposal.
+    #[account(
+        mut,
+        seeds = [b"signatory", proposal.key().as_ref(), signatory.key().as_ref()],
+        bump = signatory_record.bump,
+        has_one = signatory @ SoreinError::UnauthorizedUser
+    )]
+    pub signatory_record: Account<'info, SignatoryRecord>,
+}
+
+/// Instruction for a co-signatory to approve a draft for voting.
+pub fn sign_off_signatory(ctx: Context<SignOffSignatory>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let signatory_record = &mut ctx.accounts.signatory_record;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if proposal.status != ProposalStatus::Draft {
+        return err!(SoreinError::ProposalNotEditable);
+    }
+    if proposal.is_expired(Clock::get()?.unix_timestamp) {
+        return err!(SoreinError::ProposalExpired);
+    }
+    if signatory_record.signed_off {
+        return err!(SoreinError::AlreadySignedOff);
+    }
+
+    signatory_record.signed_off = true;
+    proposal.signed_off_count += 1;
+
+    msg!("Signatory {} signed off proposal {}", ctx.accounts.signatory.key(), proposal_id);
+    Ok(())
+}
+
+/// Context for the creator opening a draft proposal for voting.
+#[derive(Accounts)]
+pub struct SignOffProposal<'info> {
+    /// The proposal creator.
+    pub creator: Signer<'info>,
+    /// The platform configuration account to ensure governance is enabled.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The draft proposal to open.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Instruction to sign off a draft once every co-signatory has, which opens voting.
+/// The quorum denominator, voting power snapshot and proposer reward are taken now, so stake added later
+/// can't vote and later reward changes don't apply.
+/// The denominator is raw staked supply, matched by the raw stake recorded behind each vote.
+pub fn sign_off_proposal(ctx: Context<SignOffProposal>, proposal_id: u64) -> Result<()> {
+    let platform_config = &ctx.accounts.platform_config;
+    if !platform_config.governance_enabled {
+        return err!(SoreinError::GovernanceDisabled);
+    }
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if proposal.status != ProposalStatus::Draft {
+        return err!(SoreinError::ProposalNotEditable);
+    }
+    let clock = Clock::get()?;
+    if proposal.is_expired(clock.unix_timestamp) {
+        return err!(SoreinError::ProposalExpired);
+    }
+    if proposal.signed_off_count < proposal.signatory_count {
+        return err!(SoreinError::SignatoriesPending);
+    }
+
+    proposal.total_voting_supply = platform_config.total_staked;
+    proposal.proposer_reward = platform_config.proposer_reward;
+    proposal.snapshot_slot = clock.slot.saturating_sub(1);
+    proposal.start_time = clock.unix_timestamp;
+    proposal.end_time = clock.unix_timestamp
+        .checked_add(proposal.voting_duration as i64)
+        .ok_or(SoreinError::ArithmeticError)?;
+    proposal.status = ProposalStatus::Active;
+
+    emit!(ProposalSignedOff {
+        proposal_id,
+        start_time: proposal.start_time,
+        end_time: proposal.end_time,
+    });
+
+    Ok(())
+}
+
+/// Context for the creator withdrawing a proposal.
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    /// The proposal creator.
+    pub creator: Signer<'info>,
+    /// The proposal to cancel.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Instruction for the creator to withdraw a draft, or an open proposal before any votes are cast.
+/// Either way the deposit is refunded when it is settled.
+pub fn cancel_proposal(ctx: Context<CancelProposal>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    let cancellable = match proposal.status {
+        ProposalStatus::Draft => true,
+        ProposalStatus::Active => proposal.voter_count == 0,
+        _ => false,
+    };
+    if !cancellable {
+        return err!(SoreinError::ProposalNotCancellable);
+    }
+
+    let clock = Clock::get()?;
+    proposal.status = ProposalStatus::Cancelled;
+
+    emit!(ProposalCancelled {
+        proposal_id,
+        cancelled_by: ctx.accounts.creator.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 /// Context for casting a vote on a proposal.
 #[derive(Accounts)]
 pub struct CastVote<'info> {
@@ -95,14 +505,51 @@ pub struct CastVote<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
     /// The platform configuration account to ensure governance is enabled.
-    #[account(has_one = authority @ RexoulError::UnauthorizedAccess)]
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
     pub platform_config: Account<'info, PlatformConfig>,
     /// The proposal account to vote on.
     #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
     pub proposal: Account<'info, Proposal>,
-    /// The user's stake account to determine voting power (optional placeholder).
-    #[account(mut)]
-    pub user_stake: Option<Account<'info, UserStake>>,
+    /// The voter's stake account, checked for delegation.
+    #[account(
+        seeds = [b"user-stake", voter.key().as_ref()],
+        bump = user_stake.bump,
+        constraint = user_stake.user == voter.key() @ SoreinError::UnauthorizedUser
+    )]
+    pub user_stake: Account<'info, UserStake>,
+    /// The voter's stake history, read at the proposal's snapshot slot. Omitted by delegates who never staked.
+    #[account(seeds = [b"stake-checkpoints", voter.key().as_ref()], bump = stake_checkpoints.bump)]
+    pub stake_checkpoints: Option<Account<'info, StakeCheckpoints>>,
+    /// The proposal's ballot box, required when the proposal is ranked-choice.
+    #[account(mut, seeds = [b"ballot-box", proposal.key().as_ref()], bump = ballot_box.bump)]
+    pub ballot_box: Option<Account<'info, BallotBox>>,
+    /// The voter's record for this proposal, created on first vote.
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteRecord::SPACE,
+        seeds = [b"vote-record", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    /// The reward epoch the proposal's voting closes in, created by its first voter.
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = GovernanceRewardEpoch::SPACE,
+        seeds = [b"gov-reward-epoch", proposal.reward_epoch().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reward_epoch: Account<'info, GovernanceRewardEpoch>,
+    /// The voter's participation in that reward epoch, created by their first vote in it.
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoterRewardEpoch::SPACE,
+        seeds = [b"voter-reward-epoch", proposal.reward_epoch().to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_reward_epoch: Account<'info, VoterRewardEpoch>,
     /// The system program for account operations.
     pub system_program: Program<'info, System>,
 }
@@ -112,24 +559,30 @@ impl<'info> CastVote<'info> {
     pub fn validate(&self) -> Result<()> { 
         // Check if governance is enabled.
         if !self.platform_config.governance_enabled {
-            return err!(RexoulError::GovernanceDisabled);
+            return err!(SoreinError::GovernanceDisabled);
         }
         // Check if the proposal is active.
         let clock = Clock::get()?;
-        if self.proposal.status != 0 || clock.unix_timestamp < self.proposal.start_time || clock.unix_timestamp > self.proposal.end_time {
-            return err!(RexoulError::ProposalNotActive);
+        if !self.proposal.is_voting_open(clock.unix_timestamp) {
+            return err!(SoreinError::ProposalNotActive);
+        }
+        // A populated vote record means this voter has already voted; use change_vote instead.
+        if self.vote_record.voter != Pubkey::default() {
+            return err!(SoreinError::AlreadyVoted);
         }
-        // Placeholder for checking if the voter has already voted.
-        // In a real implementation, track votes per user to prevent double voting.
         Ok(())
     }
 }
 
-/// Instruction to cast a vote on a proposal.
+/// Instruction to cast a vote on a proposal. `choices` holds one option for single-choice proposals,
+/// up to the proposal's max_choices for approval voting, or a ranking (most preferred first) for
+/// ranked-choice; a veto is cast as VETO_VOTE_OPTION on its own.
+/// Delegates pass the UserStake and StakeCheckpoints accounts of each delegator, in pairs,
+/// as remaining accounts to vote with their power.
 pub fn cast_vote(
     ctx: Context<CastVote>,
     proposal_id: u64,
-    vote_option: u8,
+    choices: Vec<u8>,
 ) -> Result<()> {
     // Validate the input and context.
     ctx.accounts.validate()?;
@@ -137,112 +590,1248 @@ pub fn cast_vote(
     let proposal = &mut ctx.accounts.proposal;
     // Ensure the proposal ID matches (redundant but for clarity).
     if proposal.id != proposal_id {
-        return err!(RexoulError::InvalidProposal);
+        return err!(SoreinError::InvalidProposal);
     }
-    // Ensure the vote option is valid.
-    if vote_option as usize >= proposal.options.len() {
-        return err!(RexoulError::InvalidVoteOption);
+    // Ensure the choices form a valid ballot for the proposal's vote type.
+    if !proposal.vote_type.is_valid_ballot(&choices, proposal.options.len()) {
+        return err!(SoreinError::InvalidVoteOption);
     }
 
     let clock = Clock::get()?;
-    // Calculate voting weight (placeholder logic).
-    // In a real implementation, derive weight from staked amount in user_stake.
-    let vote_weight = 1; // Simplified for now.
+    // Calculate voting weight from the voter's stake at the proposal's snapshot slot,
+    // plus the power of any delegators passed as remaining accounts.
+    let (vote_weight, vote_stake) = total_voting_power(
+        &ctx.accounts.voter.key(),
+        &ctx.accounts.user_stake,
+        ctx.accounts.stake_checkpoints.as_deref(),
+        &ctx.accounts.platform_config,
+        proposal,
+        ctx.remaining_accounts,
+    )?;
+    if vote_weight == 0 {
+        return err!(SoreinError::InsufficientVotingPower);
+    }
 
     // Record the vote.
-    proposal.votes[vote_option as usize] += vote_weight;
+    add_votes(proposal, ctx.accounts.ballot_box.as_deref_mut(), &choices, vote_weight)?;
+    proposal.quorum_votes = proposal.quorum_votes.checked_add(vote_stake).ok_or(SoreinError::ArithmeticError)?;
+    proposal.voter_count = proposal.voter_count.checked_add(1).ok_or(SoreinError::ArithmeticError)?;
+    ctx.accounts.vote_record.init(
+        proposal.key(),
+        ctx.accounts.voter.key(),
+        choices.clone(),
+        vote_weight,
+        vote_stake,
+        clock.unix_timestamp,
+        proposal.reward_epoch(),
+        ctx.bumps.vote_record,
+    );
+
+    // Count the vote towards the epoch's voting rewards, up to the voter's largest vote in the epoch.
+    let platform_config = &ctx.accounts.platform_config;
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+    if reward_epoch.epoch == 0 {
+        reward_epoch.init(
+            proposal.reward_epoch(),
+            platform_config.voting_reward_budget,
+            platform_config.proposer_reward_epoch_cap,
+            ctx.bumps.reward_epoch,
+        );
+    }
+    let voter_reward_epoch = &mut ctx.accounts.voter_reward_epoch;
+    if voter_reward_epoch.voter == Pubkey::default() {
+        voter_reward_epoch.init(ctx.accounts.voter.key(), proposal.reward_epoch(), ctx.bumps.voter_reward_epoch);
+    }
+    let increase = voter_reward_epoch.raise_weight(vote_weight);
+    reward_epoch.total_weight = reward_epoch.total_weight.checked_add(increase).ok_or(SoreinError::ArithmeticError)?;
 
     // Emit an event for vote casting.
     emit!(VoteCast {
         proposal_id,
         voter: ctx.accounts.voter.key(),
         timestamp: clock.unix_timestamp,
-        vote_option,
-        vote_weight: vote_weight as u64,
+        choices,
+        vote_weight,
     });
 
     Ok(())
 }
 
-/// Context for finalizing a proposal after voting ends.
+/// Context for changing an existing vote while voting is open.
 #[derive(Accounts)]
-pub struct FinalizeProposal<'info> {
-    /// The caller who finalizes the proposal (can be anyone since it's permissionless).
-    #[account(mut)]
-    pub caller: Signer<'info>,
+pub struct ChangeVote<'info> {
+    /// The voter who cast the original vote.
+    pub voter: Signer<'info>,
     /// The platform configuration account to ensure governance is enabled.
-    #[account(has_one = authority @ RexoulError::UnauthorizedAccess)]
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
     pub platform_config: Account<'info, PlatformConfig>,
-    /// The proposal account to finalize.
+    /// The proposal account the vote was cast on.
     #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
     pub proposal: Account<'info, Proposal>,
-    /// The system program for account operations.
-    pub system_program: Program<'info, System>,
-}
-
-impl<'info> FinalizeProposal<'info> {
-    /// Validates that the proposal can be finalized.
-    pub fn validate(&self) -> Result<()> {
-        // Check if governance is enabled.
-        if !self.platform_config.governance_enabled {
-            return err!(RexoulError::GovernanceDisabled);
-        }
-        // Check if the proposal is still active and voting period has ended.
-        let clock = Clock::get()?;
-        if self.proposal.status != 0 {
-            return err!(RexoulError::ProposalAlreadyFinalized);
-        }
-        if clock.unix_timestamp <= self.proposal.end_time {
-            return err!(RexoulError::VotingPeriodNotEnded);
-        }
-        Ok(())
-    }
+    /// The voter's stake account, checked for delegation.
+    #[account(
+        seeds = [b"user-stake", voter.key().as_ref()],
+        bump = user_stake.bump,
+        constraint = user_stake.user == voter.key() @ SoreinError::UnauthorizedUser
+    )]
+    pub user_stake: Account<'info, UserStake>,
+    /// The voter's stake history, read at the proposal's snapshot slot. Omitted by delegates who never staked.
+    #[account(seeds = [b"stake-checkpoints", voter.key().as_ref()], bump = stake_checkpoints.bump)]
+    pub stake_checkpoints: Option<Account<'info, StakeCheckpoints>>,
+    /// The proposal's ballot box, required when the proposal is ranked-choice.
+    #[account(mut, seeds = [b"ballot-box", proposal.key().as_ref()], bump = ballot_box.bump)]
+    pub ballot_box: Option<Account<'info, BallotBox>>,
+    /// The voter's existing record for this proposal.
+    #[account(
+        mut,
+        seeds = [b"vote-record", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        has_one = voter @ SoreinError::UnauthorizedUser
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    /// The reward epoch the vote counts towards.
+    #[account(
+        mut,
+        seeds = [b"gov-reward-epoch", vote_record.reward_epoch.to_le_bytes().as_ref()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, GovernanceRewardEpoch>,
+    /// The voter's participation in that reward epoch.
+    #[account(
+        mut,
+        seeds = [b"voter-reward-epoch", vote_record.reward_epoch.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = voter_reward_epoch.bump
+    )]
+    pub voter_reward_epoch: Account<'info, VoterRewardEpoch>,
 }
 
-/// Instruction to finalize a proposal and determine the result.
-pub fn finalize_proposal(
-    ctx: Context<FinalizeProposal>,
+/// Instruction to replace an existing vote with new choices.
+/// The previous weight is removed and the vote is re-counted at the voter's snapshot voting power.
+pub fn change_vote(
+    ctx: Context<ChangeVote>,
     proposal_id: u64,
+    choices: Vec<u8>,
 ) -> Result<()> {
-    // Validate the input and context.
-    ctx.accounts.validate()?;
+    let clock = Clock::get()?;
+    if !ctx.accounts.platform_config.governance_enabled {
+        return err!(SoreinError::GovernanceDisabled);
+    }
 
     let proposal = &mut ctx.accounts.proposal;
-    // Ensure the proposal ID matches.
+    let vote_record = &mut ctx.accounts.vote_record;
     if proposal.id != proposal_id {
-        return err!(RexoulError::InvalidProposal);
+        return err!(SoreinError::InvalidProposal);
     }
-
-    let clock = Clock::get()?;
-    // Determine the winning option (highest votes).
-    let mut max_votes = 0;
-    let mut winning_option = 0;
-    for (index, &votes) in proposal.votes.iter().enumerate() {
-        if votes > max_votes {
-            max_votes = votes;
-            winning_option = index as u8;
-        }
+    if !proposal.is_voting_open(clock.unix_timestamp) {
+        return err!(SoreinError::ProposalNotActive);
+    }
+    if !proposal.vote_type.is_valid_ballot(&choices, proposal.options.len()) {
+        return err!(SoreinError::InvalidVoteOption);
     }
 
-    // Update proposal status (1 = Approved if there's a clear winner, 2 = Rejected if no votes or tied).
-    proposal.status = if max_votes > 0 { 1 } else { 2 };
+    let (vote_weight, vote_stake) = total_voting_power(
+        &ctx.accounts.voter.key(),
+        &ctx.accounts.user_stake,
+        ctx.accounts.stake_checkpoints.as_deref(),
+        &ctx.accounts.platform_config,
+        proposal,
+        ctx.remaining_accounts,
+    )?;
+    if vote_weight == 0 {
+        return err!(SoreinError::InsufficientVotingPower);
+    }
 
-    // Serialize vote summary as a string for the event (simplified).
-    let vote_summary = format!("{:?}", proposal.votes);
+    // Swap the previous weight for the new one.
+    let mut ballot_box = ctx.accounts.ballot_box.as_deref_mut();
+    remove_votes(proposal, ballot_box.as_deref_mut(), &vote_record.choices, vote_record.weight)?;
+    add_votes(proposal, ballot_box, &choices, vote_weight)?;
+    proposal.quorum_votes = proposal.quorum_votes
+        .checked_sub(vote_record.stake)
+        .and_then(|total| total.checked_add(vote_stake))
+        .ok_or(SoreinError::ArithmeticError)?;
+    let increase = ctx.accounts.voter_reward_epoch.raise_weight(vote_weight);
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+    reward_epoch.total_weight = reward_epoch.total_weight.checked_add(increase).ok_or(SoreinError::ArithmeticError)?;
+    vote_record.choices = choices.clone();
+    vote_record.weight = vote_weight;
+    vote_record.reward_weight = vote_record.reward_weight.max(vote_weight);
+    vote_record.stake = vote_stake;
+    vote_record.voted_at = clock.unix_timestamp;
 
-    // Emit an event for proposal finalization.
-    emit!(ProposalFinalized {
+    emit!(VoteCast {
         proposal_id,
+        voter: ctx.accounts.voter.key(),
         timestamp: clock.unix_timestamp,
-        result: proposal.status,
-        vote_summary,
+        choices,
+        vote_weight,
     });
 
-    // Optionally, trigger platform updates if the proposal is approved.
-    // This is a placeholder; in a real implementation, apply changes based on proposal content.
-    if proposal.status == 1 {
-        msg!("Proposal {} approved with option {}", proposal_id, winning_option);
+    Ok(())
+}
+
+/// Context for withdrawing a vote and closing the vote record.
+#[derive(Accounts)]
+pub struct RelinquishVote<'info> {
+    /// The voter who cast the vote, refunded the vote record rent.
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    /// The proposal account the vote was cast on.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The proposal's ballot box, required when the proposal is ranked-choice.
+    #[account(mut, seeds = [b"ballot-box", proposal.key().as_ref()], bump = ballot_box.bump)]
+    pub ballot_box: Option<Account<'info, BallotBox>>,
+    /// The voter's record for this proposal, closed once relinquished.
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote-record", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        has_one = voter @ SoreinError::UnauthorizedUser
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    /// The voter's participation in the reward epoch the vote counts towards.
+    #[account(
+        seeds = [b"voter-reward-epoch", vote_record.reward_epoch.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = voter_reward_epoch.bump
+    )]
+    pub voter_reward_epoch: Account<'info, VoterRewardEpoch>,
+}
+
+/// Instruction to withdraw a vote.
+/// While voting is open the vote's weight is removed from the tally; after that the result stands and
+/// only the vote record is closed, so it can no longer be used to claim the epoch's voting reward.
+/// The voter's weight in the reward epoch is only the largest of their votes and can't be recomputed
+/// here, so a vote still open for voting can't be withdrawn if it is what sets that weight.
+pub fn relinquish_vote(ctx: Context<RelinquishVote>, proposal_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &ctx.accounts.vote_record;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+
+    if proposal.is_voting_open(clock.unix_timestamp) {
+        if vote_record.reward_weight >= ctx.accounts.voter_reward_epoch.weight {
+            return err!(SoreinError::VoteHoldsRewardWeight);
+        }
+        remove_votes(proposal, ctx.accounts.ballot_box.as_deref_mut(), &vote_record.choices, vote_record.weight)?;
+        proposal.quorum_votes = proposal.quorum_votes.checked_sub(vote_record.stake).ok_or(SoreinError::ArithmeticError)?;
     }
+    proposal.voter_count = proposal.voter_count.checked_sub(1).ok_or(SoreinError::ArithmeticError)?;
+
+    msg!("Voter {} relinquished vote on proposal {}", ctx.accounts.voter.key(), proposal_id);
+    Ok(())
+}
+
+/// Total voting power of a voter at the proposal's snapshot slot: their own lockup-weighted stake
+/// plus that of every delegator passed in `delegator_accounts` as (UserStake, StakeCheckpoints) pairs.
+/// Returns the weighted power and the raw stake behind it, which is what counts towards quorum.
+/// Lockup multipliers are measured at the proposal's start, so power doesn't drift during voting.
+/// Delegations must predate the proposal's start so the same stake can never be counted both
+/// directly and through a delegate.
+fn total_voting_power(
+    voter: &Pubkey,
+    user_stake: &UserStake,
+    stake_checkpoints: Option<&StakeCheckpoints>,
+    platform_config: &PlatformConfig,
+    proposal: &Proposal,
+    delegator_accounts: &[AccountInfo],
+) -> Result<(u64, u64)> {
+    if user_stake.is_delegated() {
+        return err!(SoreinError::VotingPowerDelegated);
+    }
+    if user_stake.delegation_updated_at >= proposal.start_time {
+        return err!(SoreinError::DelegationChangedDuringVote);
+    }
+    if !delegator_accounts.len().is_multiple_of(2) || delegator_accounts.len() / 2 > user_stake.delegator_count as usize {
+        return err!(SoreinError::InvalidDelegatorAccount);
+    }
+
+    let (mut total, mut total_stake) = match stake_checkpoints {
+        Some(checkpoints) => (
+            checkpoints.voting_power_at(proposal.snapshot_slot, proposal.start_time, platform_config)?,
+            checkpoints.stake_at(proposal.snapshot_slot),
+        ),
+        None => (0, 0),
+    };
+    let mut counted: Vec<Pubkey> = Vec::with_capacity(delegator_accounts.len() / 2);
+    for pair in delegator_accounts.chunks(2) {
+        let (stake_info, checkpoints_info) = (&pair[0], &pair[1]);
+        if counted.contains(stake_info.key) {
+            return err!(SoreinError::InvalidDelegatorAccount);
+        }
+        // The owner and discriminator checks prove the account types; the seeds checks pin them to the delegator.
+        if stake_info.owner != &crate::ID || checkpoints_info.owner != &crate::ID {
+            return err!(SoreinError::InvalidDelegatorAccount);
+        }
+        let delegator_stake = UserStake::try_deserialize(&mut &stake_info.try_borrow_data()?[..])?;
+        let expected = Pubkey::create_program_address(
+            &[b"user-stake", delegator_stake.user.as_ref(), &[delegator_stake.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(SoreinError::InvalidDelegatorAccount))?;
+        if expected != *stake_info.key || delegator_stake.delegate != *voter {
+            return err!(SoreinError::InvalidDelegatorAccount);
+        }
+        if delegator_stake.delegation_updated_at >= proposal.start_time {
+            return err!(SoreinError::DelegationChangedDuringVote);
+        }
+        let delegator_checkpoints = StakeCheckpoints::try_deserialize(&mut &checkpoints_info.try_borrow_data()?[..])?;
+        let expected = Pubkey::create_program_address(
+            &[b"stake-checkpoints", delegator_stake.user.as_ref(), &[delegator_checkpoints.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(SoreinError::InvalidDelegatorAccount))?;
+        if expected != *checkpoints_info.key {
+            return err!(SoreinError::InvalidDelegatorAccount);
+        }
+        let power = delegator_checkpoints.voting_power_at(proposal.snapshot_slot, proposal.start_time, platform_config)?;
+        total = total.checked_add(power).ok_or(SoreinError::ArithmeticError)?;
+        total_stake = total_stake
+            .checked_add(delegator_checkpoints.stake_at(proposal.snapshot_slot))
+            .ok_or(SoreinError::ArithmeticError)?;
+        counted.push(*stake_info.key);
+    }
+    Ok((total, total_stake))
+}
+
+/// Returns the tally a vote option counts towards (the veto tally for VETO_VOTE_OPTION).
+fn tally_for(proposal: &mut Proposal, vote_option: u8) -> Result<&mut u64> {
+    if vote_option == VETO_VOTE_OPTION {
+        return Ok(&mut proposal.veto_votes);
+    }
+    proposal.votes.get_mut(vote_option as usize).ok_or(error!(SoreinError::InvalidVoteOption))
+}
+
+/// How a ballot's weight is spread over the per-option tallies: split evenly across approval choices,
+/// otherwise counted in full for the single choice or first preference.
+fn vote_shares(proposal: &Proposal, choices: &[u8], weight: u64) -> Vec<(u8, u64)> {
+    match proposal.vote_type {
+        VoteType::Approval { .. } => choices.iter().copied().zip(math::split_vote_weight(weight, choices.len())).collect(),
+        _ => choices.first().map(|choice| vec![(*choice, weight)]).unwrap_or_default(),
+    }
+}
+
+/// Whether a ballot's full ranking must be kept in the ballot box for the runoff.
+fn is_ranked_ballot(proposal: &Proposal, choices: &[u8]) -> bool {
+    proposal.vote_type == VoteType::RankedChoice && choices != [VETO_VOTE_OPTION]
+}
+
+/// Adds a ballot's voting power to the tallies, and its ranking to the ballot box for ranked-choice proposals.
+fn add_votes(proposal: &mut Proposal, ballot_box: Option<&mut BallotBox>, choices: &[u8], weight: u64) -> Result<()> {
+    for (choice, share) in vote_shares(proposal, choices, weight) {
+        let votes = tally_for(proposal, choice)?;
+        *votes = votes.checked_add(share).ok_or(SoreinError::ArithmeticError)?;
+    }
+    if is_ranked_ballot(proposal, choices) {
+        ballot_box.ok_or(error!(SoreinError::InvalidAccount))?.add(choices, weight)?;
+    }
+    Ok(())
+}
+
+/// Removes previously counted voting power from the tallies and the ballot box.
+fn remove_votes(proposal: &mut Proposal, ballot_box: Option<&mut BallotBox>, choices: &[u8], weight: u64) -> Result<()> {
+    for (choice, share) in vote_shares(proposal, choices, weight) {
+        let votes = tally_for(proposal, choice)?;
+        *votes = votes.checked_sub(share).ok_or(SoreinError::ArithmeticError)?;
+    }
+    if is_ranked_ballot(proposal, choices) {
+        ballot_box.ok_or(error!(SoreinError::InvalidAccount))?.remove(choices, weight)?;
+    }
+    Ok(())
+}
+
+/// Context for finalizing a proposal after voting ends.
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    /// The caller who finalizes the proposal (can be anyone since it's permissionless).
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    /// The platform configuration account to ensure governance is enabled.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The proposal account to finalize.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The proposal's ballot box, required when the proposal is ranked-choice.
+    #[account(seeds = [b"ballot-box", proposal.key().as_ref()], bump = ballot_box.bump)]
+    pub ballot_box: Option<Account<'info, BallotBox>>,
+    /// The system program for account operations.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FinalizeProposal<'info> {
+    /// Validates that the proposal can be finalized.
+    pub fn validate(&self) -> Result<()> {
+        // Check if governance is enabled.
+        if !self.platform_config.governance_enabled {
+            return err!(SoreinError::GovernanceDisabled);
+        }
+        // Expired drafts and stale proposals are closed out without a tally, and queued proposals
+        // that missed their execution window are closed out with theirs.
+        let clock = Clock::get()?;
+        if self.proposal.is_expired(clock.unix_timestamp)
+            || self.proposal.is_execution_expired(clock.unix_timestamp, self.platform_config.execution_grace_period)
+        {
+            return Ok(());
+        }
+        // Check if the proposal is still active and voting period has ended.
+        if self.proposal.status == ProposalStatus::Draft {
+            return err!(SoreinError::ProposalNotActive);
+        }
+        if self.proposal.status != ProposalStatus::Active {
+            return err!(SoreinError::ProposalAlreadyFinalized);
+        }
+        if clock.unix_timestamp <= self.proposal.end_time {
+            return err!(SoreinError::VotingPeriodNotEnded);
+        }
+        // The runoff can't be tallied without the rankings.
+        if self.proposal.vote_type == VoteType::RankedChoice && self.ballot_box.is_none() {
+            return err!(SoreinError::InvalidAccount);
+        }
+        Ok(())
+    }
+}
+
+/// Instruction to finalize a proposal and determine the result. A queued proposal whose execution window
+/// has passed is finalized again as ExecutionExpired, so it can be closed and its deposit settled.
+pub fn finalize_proposal(
+    ctx: Context<FinalizeProposal>,
+    proposal_id: u64,
+) -> Result<()> {
+    // Validate the input and context.
+    ctx.accounts.validate()?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    // Ensure the proposal ID matches.
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+
+    let clock = Clock::get()?;
+    // Apply quorum, veto and approval thresholds to the final tally, unless it was left too long.
+    let grace_period = ctx.accounts.platform_config.execution_grace_period;
+    let (status, winning_option) = if proposal.is_execution_expired(clock.unix_timestamp, grace_period) {
+        (ProposalStatus::ExecutionExpired, proposal.winning_option)
+    } else if proposal.is_expired(clock.unix_timestamp) {
+        (ProposalStatus::Expired, None)
+    } else {
+        proposal.tally(ctx.accounts.ballot_box.as_deref())
+    };
+    proposal.status = status;
+    proposal.winning_option = winning_option;
+
+    // Proposals that passed with an action or instructions wait out the timelock before they can be executed.
+    let executable = proposal.has_payload() && winning_option == Some(APPROVE_OPTION_INDEX);
+    if status == ProposalStatus::Approved && executable {
+        let delay = ctx.accounts.platform_config.execution_delay_for(proposal.proposal_type);
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = clock.unix_timestamp.checked_add(delay).ok_or(SoreinError::ArithmeticError)?;
+    }
+
+    // Serialize vote summary as a string for the event (simplified).
+    let vote_summary = format!("{:?} veto: {}", proposal.votes, proposal.veto_votes);
+
+    // Emit an event for proposal finalization.
+    emit!(ProposalFinalized {
+        proposal_id,
+        timestamp: clock.unix_timestamp,
+        result: proposal.status,
+        vote_summary,
+    });
+
+    // Queued proposals are applied separately via execute_proposal once their eta passes.
+    if proposal.status == ProposalStatus::Queued {
+        emit!(ProposalQueued {
+            proposal_id,
+            timestamp: clock.unix_timestamp,
+            eta: proposal.eta,
+        });
+    } else if let Some(winning_option) = proposal.winning_option {
+        msg!("Proposal {} approved with option {}", proposal_id, winning_option);
+    }
+
+    Ok(())
+}
+
+/// Context for completing a queued proposal: applies its action once all instructions have run.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// The caller who executes the proposal (can be anyone since it's permissionless).
+    pub executor: Signer<'info>,
+    /// The platform configuration account the action is applied to.
+    #[account(mut, seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal to execute.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    /// Validates that the proposal is queued, inside its execution window and has no pending instructions.
+    pub fn validate(&self) -> Result<()> {
+        check_ready_for_completion(&self.proposal, &self.platform_config)
+    }
+}
+
+/// Instruction to apply a queued proposal's action to the platform configuration and mark it executed.
+pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+    // Validate the input and context.
+    ctx.accounts.validate()?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+
+    let clock = Clock::get()?;
+    let action = proposal.action;
+    if let Some(action) = action.as_ref() {
+        ctx.accounts.platform_config.apply_action(action, clock.unix_timestamp)?;
+    }
+
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+
+    // Emit an event for proposal execution.
+    emit!(ProposalExecuted {
+        proposal_id,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+        action,
+    });
+
+    Ok(())
+}
+
+/// Checks that a queued proposal is inside its execution window and all of its instructions have run.
+pub(crate) fn check_ready_for_completion(proposal: &Proposal, platform_config: &PlatformConfig) -> Result<()> {
+    check_execution_window(proposal, platform_config)?;
+    if proposal.executed_instruction_count < proposal.instruction_count {
+        return err!(SoreinError::InstructionsNotExecuted);
+    }
+    Ok(())
+}
+
+/// Checks that a proposal is queued and that the current time is between its eta and the end of the grace period.
+fn check_execution_window(proposal: &Proposal, platform_config: &PlatformConfig) -> Result<()> {
+    if proposal.status == ProposalStatus::Executed {
+        return err!(SoreinError::ProposalAlreadyExecuted);
+    }
+    if proposal.status != ProposalStatus::Queued || !proposal.has_payload() {
+        return err!(SoreinError::ProposalNotExecutable);
+    }
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < proposal.eta {
+        return err!(SoreinError::TimelockNotElapsed);
+    }
+    let expires_at = proposal.eta
+        .checked_add(platform_config.execution_grace_period)
+        .ok_or(SoreinError::ArithmeticError)?;
+    if clock.unix_timestamp > expires_at {
+        return err!(SoreinError::ExecutionWindowExpired);
+    }
+    Ok(())
+}
+
+/// Context for attaching an instruction to a proposal.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, index: u16)]
+pub struct InsertProposalInstruction<'info> {
+    /// The proposal creator, the only account allowed to attach instructions.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// The proposal the instruction is attached to.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// The instruction account to be initialized.
+    #[account(
+        init,
+        payer = creator,
+        space = ProposalInstruction::SPACE,
+        seeds = [b"proposal-instruction", proposal.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to attach an instruction (program, accounts and an initial data chunk) to a proposal.
+/// Instructions are appended in order and can only be added while the proposal is a draft.
+pub fn insert_proposal_instruction(
+    ctx: Context<InsertProposalInstruction>,
+    proposal_id: u64,
+    index: u16,
+    program_id: Pubkey,
+    accounts: Vec<ProposalAccountMeta>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_proposal_editable(proposal)?;
+    if index != proposal.instruction_count || index >= MAX_PROPOSAL_INSTRUCTIONS {
+        return err!(SoreinError::InvalidInstructionIndex);
+    }
+    if accounts.len() > MAX_INSTRUCTION_ACCOUNTS || data.len() > MAX_INSTRUCTION_DATA_LENGTH {
+        return err!(SoreinError::InstructionTooLarge);
+    }
+
+    let proposal_instruction = &mut ctx.accounts.proposal_instruction;
+    proposal_instruction.init(
+        proposal.key(),
+        ctx.accounts.creator.key(),
+        index,
+        program_id,
+        accounts,
+        ctx.bumps.proposal_instruction,
+    );
+    proposal_instruction.data = data;
+    proposal.instruction_count += 1;
+
+    msg!("Instruction {} for program {} attached to proposal {}", index, program_id, proposal_id);
+    Ok(())
+}
+
+/// Context for appending a data chunk to an attached instruction.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, index: u16)]
+pub struct AppendInstructionData<'info> {
+    /// The proposal creator, the only account allowed to edit instructions.
+    pub creator: Signer<'info>,
+    /// The proposal the instruction is attached to.
+    #[account(
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// The instruction whose data is extended.
+    #[account(
+        mut,
+        seeds = [b"proposal-instruction", proposal.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = proposal_instruction.bump
+    )]
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+}
+
+/// Instruction to append a chunk of instruction data, for payloads too large for a single transaction.
+pub fn append_instruction_data(
+    ctx: Context<AppendInstructionData>,
+    proposal_id: u64,
+    _index: u16,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_proposal_editable(proposal)?;
+
+    let proposal_instruction = &mut ctx.accounts.proposal_instruction;
+    if proposal_instruction.data.len() + chunk.len() > MAX_INSTRUCTION_DATA_LENGTH {
+        return err!(SoreinError::InstructionTooLarge);
+    }
+    proposal_instruction.data.extend_from_slice(&chunk);
+
+    Ok(())
+}
+
+/// Checks that a proposal can still be edited: it must be a draft that has not expired or been signed off by anyone.
+fn check_proposal_editable(proposal: &Proposal) -> Result<()> {
+    if proposal.status != ProposalStatus::Draft || proposal.signed_off_count > 0 {
+        return err!(SoreinError::ProposalNotEditable);
+    }
+    if proposal.is_expired(Clock::get()?.unix_timestamp) {
+        return err!(SoreinError::ProposalExpired);
+    }
+    Ok(())
+}
+
+/// Context for invoking one of a queued proposal's instructions.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, index: u16)]
+pub struct ExecuteProposalInstruction<'info> {
+    /// The caller who executes the instruction (can be anyone since it's permissionless).
+    pub executor: Signer<'info>,
+    /// The platform configuration account holding the execution grace period.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal the instruction belongs to.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The instruction to invoke.
+    #[account(
+        mut,
+        seeds = [b"proposal-instruction", proposal.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = proposal_instruction.bump
+    )]
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+    /// CHECK: PDA with no data that signs the invoked instruction on behalf of governance.
+    #[account(seeds = [GOVERNANCE_AUTHORITY_SEED], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+}
+
+/// Instruction to invoke an attached instruction, signed by the governance authority PDA.
+/// The target program and every account in the instruction must be passed as remaining accounts.
+pub fn execute_proposal_instruction(
+    ctx: Context<ExecuteProposalInstruction>,
+    proposal_id: u64,
+    index: u16,
+) -> Result<()> {
+    if ctx.accounts.proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_execution_window(&ctx.accounts.proposal, &ctx.accounts.platform_config)?;
+    if ctx.accounts.proposal_instruction.executed {
+        return err!(SoreinError::InstructionAlreadyExecuted);
+    }
+
+    let proposal_instruction = &ctx.accounts.proposal_instruction;
+    let instruction = Instruction {
+        program_id: proposal_instruction.program_id,
+        accounts: proposal_instruction
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: proposal_instruction.data.clone(),
+    };
+    let seeds = &[GOVERNANCE_AUTHORITY_SEED, &[ctx.bumps.governance_authority]];
+    let signer = &[&seeds[..]];
+    invoke_signed(&instruction, ctx.remaining_accounts, signer)?;
+
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    ctx.accounts.proposal_instruction.executed = true;
+    proposal.executed_instruction_count += 1;
+
+    // Emit an event for instruction execution.
+    emit!(ProposalInstructionExecuted {
+        proposal_id,
+        index,
+        program_id: instruction.program_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for the guardian cancelling a proposal before it is executed.
+#[derive(Accounts)]
+pub struct GuardianCancelProposal<'info> {
+    /// The guardian configured on the platform.
+    pub guardian: Signer<'info>,
+    /// The platform configuration account holding the guardian key.
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        has_one = guardian @ SoreinError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The proposal to cancel.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Instruction for the guardian to cancel a draft, active or queued proposal.
+/// This is the escape hatch for contentious or malicious changes during the timelock; the deposit is slashed.
+pub fn guardian_cancel_proposal(ctx: Context<GuardianCancelProposal>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if !matches!(proposal.status, ProposalStatus::Draft | ProposalStatus::Active | ProposalStatus::Queued) {
+        return err!(SoreinError::ProposalNotCancellable);
+    }
+    // A proposal past its execution window can no longer run, so it is finalized as expired rather than slashed.
+    let clock = Clock::get()?;
+    if proposal.is_execution_expired(clock.unix_timestamp, ctx.accounts.platform_config.execution_grace_period) {
+        return err!(SoreinError::ProposalNotCancellable);
+    }
+
+    proposal.status = ProposalStatus::Cancelled;
+    proposal.cancelled_by_guardian = true;
+
+    // Emit an event for proposal cancellation.
+    emit!(ProposalCancelled {
+        proposal_id,
+        cancelled_by: ctx.accounts.guardian.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for the guardian flagging a proposal as spam.
+#[derive(Accounts)]
+pub struct FlagProposalSpam<'info> {
+    /// The guardian configured on the platform.
+    pub guardian: Signer<'info>,
+    /// The platform configuration account holding the guardian key.
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        has_one = guardian @ SoreinError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The proposal to flag.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Instruction for the guardian to flag a proposal as spam so its deposit is slashed.
+/// Drafts and proposals still being voted on or waiting in the timelock are cancelled as well.
+pub fn flag_proposal_spam(ctx: Context<FlagProposalSpam>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if proposal.deposit_settled {
+        return err!(SoreinError::DepositAlreadySettled);
+    }
+    if proposal.status == ProposalStatus::Executed {
+        return err!(SoreinError::ProposalAlreadyExecuted);
+    }
+
+    let clock = Clock::get()?;
+    proposal.flagged_spam = true;
+    if matches!(proposal.status, ProposalStatus::Draft | ProposalStatus::Active | ProposalStatus::Queued) {
+        proposal.status = ProposalStatus::Cancelled;
+    }
+
+    emit!(ProposalFlaggedSpam {
+        proposal_id,
+        flagged_by: ctx.accounts.guardian.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for refunding or slashing a proposal deposit.
+#[derive(Accounts)]
+pub struct SettleProposalDeposit<'info> {
+    /// The caller who settles the deposit (can be anyone since it's permissionless).
+    pub caller: Signer<'info>,
+    /// The platform configuration account holding the stake mint.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The proposal whose deposit is settled; it owns the escrow.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::InvalidAccount
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// CHECK: The proposal creator, checked against the proposal; receives the escrow rent.
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    /// The stake mint the deposit was paid in.
+    #[account(
+        address = platform_config.stake_mint @ SoreinError::InvalidAccount,
+        mint::token_program = token_program
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    /// The escrow holding the deposit, closed once settled.
+    #[account(
+        mut,
+        seeds = [b"proposal-deposit", proposal.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = proposal,
+        token::token_program = token_program
+    )]
+    pub deposit_escrow: InterfaceAccount<'info, TokenAccount>,
+    /// The creator's token account a refund is paid to.
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = creator,
+        token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The treasury owning the vault a slashed deposit is paid to.
+    #[account(seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    /// The treasury vault for the stake mint.
+    #[account(
+        mut,
+        seeds = [b"treasury-vault", stake_mint.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// The token program owning the stake mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Instruction to settle a proposal deposit once voting is over. The deposit is refunded
+/// to the creator if the vote reached quorum and was neither vetoed (by voters or the council), cancelled by
+/// the guardian nor flagged as spam, and slashed to the treasury otherwise. The escrow is closed and its rent returned to the creator.
+pub fn settle_proposal_deposit(ctx: Context<SettleProposalDeposit>, proposal_id: u64) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if proposal.deposit_settled {
+        return err!(SoreinError::DepositAlreadySettled);
+    }
+    if matches!(proposal.status, ProposalStatus::Draft | ProposalStatus::Active) {
+        return err!(SoreinError::ProposalNotFinalized);
+    }
+
+    let refunded = proposal.deposit_refundable();
+    let destination = if refunded {
+        ctx.accounts.creator_token_account.to_account_info()
+    } else {
+        ctx.accounts.treasury_vault.to_account_info()
+    };
+
+    // Move the whole escrow balance and close it, signed by the proposal PDA.
+    let id_bytes = proposal.id.to_le_bytes();
+    let seeds = &[b"proposal".as_ref(), id_bytes.as_ref(), &[proposal.bump]];
+    let signer = &[&seeds[..]];
+    let amount = ctx.accounts.deposit_escrow.amount;
+    if amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.deposit_escrow.to_account_info(),
+            mint: ctx.accounts.stake_mint.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.proposal.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.stake_mint.decimals)?;
+    }
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.deposit_escrow.to_account_info(),
+        destination: ctx.accounts.creator.to_account_info(),
+        authority: ctx.accounts.proposal.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::close_account(cpi_ctx)?;
+
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.deposit_settled = true;
+
+    emit!(ProposalDepositSettled {
+        proposal_id,
+        creator: proposal.creator,
+        amount,
+        refunded,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for closing a proposal that has reached a final status.
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    /// The proposal creator, refunded the proposal and ballot box rent.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// The proposal to close.
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// The proposal's ballot box, closed with it when the proposal is ranked-choice.
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"ballot-box", proposal.key().as_ref()],
+        bump = ballot_box.bump
+    )]
+    pub ballot_box: Option<Account<'info, BallotBox>>,
+}
+
+/// Instruction to reclaim the rent of a proposal once it is final, its deposit has been settled and the
+/// voting reward claim period of its epoch is over.
+/// Voters close their own vote records afterwards with close_vote_record, and the creator closes
+/// attached instructions and signatory records with close_proposal_instruction and close_signatory_record.
+pub fn close_proposal(ctx: Context<CloseProposal>, proposal_id: u64) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if !proposal.is_closable() || !proposal.deposit_settled {
+        return err!(SoreinError::ProposalNotClosable);
+    }
+    // Voters need the proposal to claim their voting reward
+    if Clock::get()?.unix_timestamp < proposal.reward_claims_end() {
+        return err!(SoreinError::ProposalNotClosable);
+    }
+    if proposal.vote_type == VoteType::RankedChoice && ctx.accounts.ballot_box.is_none() {
+        return err!(SoreinError::InvalidAccount);
+    }
+
+    msg!("Proposal {} closed by {}", proposal_id, ctx.accounts.creator.key());
+    Ok(())
+}
+
+/// Context for closing a vote record whose proposal has been closed.
+#[derive(Accounts)]
+pub struct CloseVoteRecord<'info> {
+    /// The voter, refunded the vote record rent.
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    /// CHECK: The closed proposal the vote was cast on, checked against the vote record.
+    #[account(address = vote_record.proposal @ SoreinError::InvalidProposal)]
+    pub proposal: UncheckedAccount<'info>,
+    /// The vote record to close.
+    #[account(mut, close = voter, has_one = voter @ SoreinError::UnauthorizedUser)]
+    pub vote_record: Account<'info, VoteRecord>,
+}
+
+/// Instruction to reclaim the rent of a vote record after its proposal was closed.
+/// While the proposal still exists, relinquish_vote closes the record instead.
+pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+    if !is_account_closed(&ctx.accounts.proposal) {
+        return err!(SoreinError::ProposalNotClosable);
+    }
+
+    msg!("Vote record of {} closed", ctx.accounts.voter.key());
+    Ok(())
+}
+
+/// Context for closing an attached instruction once it has run or its proposal has been closed.
+#[derive(Accounts)]
+pub struct CloseProposalInstruction<'info> {
+    /// The proposal creator, refunded the instruction rent.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// CHECK: The proposal the instruction is attached to, checked against the instruction.
+    #[account(address = proposal_instruction.proposal @ SoreinError::InvalidProposal)]
+    pub proposal: UncheckedAccount<'info>,
+    /// The instruction to close.
+    #[account(mut, close = creator, has_one = creator @ SoreinError::UnauthorizedUser)]
+    pub proposal_instruction: Account<'info, ProposalInstruction>,
+}
+
+/// Instruction to reclaim the rent of an attached instruction after it was executed or its proposal was closed.
+pub fn close_proposal_instruction(ctx: Context<CloseProposalInstruction>) -> Result<()> {
+    let proposal_instruction = &ctx.accounts.proposal_instruction;
+    if !proposal_instruction.executed && !is_account_closed(&ctx.accounts.proposal) {
+        return err!(SoreinError::ProposalNotClosable);
+    }
+
+    msg!("Instruction {} of proposal {} closed", proposal_instruction.index, proposal_instruction.proposal);
+    Ok(())
+}
+
+/// Context for closing a signatory record whose proposal has been closed.
+#[derive(Accounts)]
+pub struct CloseSignatoryRecord<'info> {
+    /// The proposal creator, refunded the signatory record rent.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// CHECK: The closed proposal the signatory was added to, checked against the signatory record.
+    #[account(address = signatory_record.proposal @ SoreinError::InvalidProposal)]
+    pub proposal: UncheckedAccount<'info>,
+    /// The signatory record to close.
+    #[account(mut, close = creator, has_one = creator @ SoreinError::UnauthorizedUser)]
+    pub signatory_record: Account<'info, SignatoryRecord>,
+}
+
+/// Instruction to reclaim the rent of a signatory record after its proposal was closed.
+/// While the proposal is still a draft, remove_signatory closes the record instead.
+pub fn close_signatory_record(ctx: Context<CloseSignatoryRecord>) -> Result<()> {
+    if !is_account_closed(&ctx.accounts.proposal) {
+        return err!(SoreinError::ProposalNotClosable);
+    }
+
+    msg!("Signatory record of {} closed", ctx.accounts.signatory_record.signatory);
+    Ok(())
+}
+
+/// Whether a proposal account has been closed (its rent reclaimed and ownership returned to the system program).
+fn is_account_closed(proposal: &AccountInfo) -> bool {
+    proposal.owner != &crate::ID || proposal.data_is_empty()
+}
+
+/// Context for delegating voting power to another user.
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    /// The user delegating their voting power.
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    /// The platform configuration account holding the minimum stake.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The delegator's stake account.
+    #[account(
+        mut,
+        seeds = [b"user-stake", delegator.key().as_ref()],
+        bump = delegator_stake.bump,
+        constraint = delegator_stake.user == delegator.key() @ SoreinError::UnauthorizedUser
+    )]
+    pub delegator_stake: Account<'info, UserStake>,
+    /// The delegate, who co-signs to accept the delegation.
+    pub delegate: Signer<'info>,
+    /// The delegate's stake account, created if the delegate has never staked.
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = UserStake::SPACE,
+        seeds = [b"user-stake", delegate.key().as_ref()],
+        bump
+    )]
+    pub delegate_stake: Account<'info, UserStake>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to delegate voting power. The delegate must accept by co-signing, and the delegator must
+/// hold at least the platform's minimum stake so dust delegations can't fill a delegate's slots.
+/// The delegator cannot vote directly until they undelegate, and the delegation only counts on
+/// proposals that start after it.
+pub fn delegate_votes(ctx: Context<DelegateVotes>) -> Result<()> {
+    let clock = Clock::get()?;
+    let delegator_key = ctx.accounts.delegator.key();
+    let delegate_key = ctx.accounts.delegate.key();
+    let delegator_stake = &mut ctx.accounts.delegator_stake;
+    let delegate_stake = &mut ctx.accounts.delegate_stake;
+
+    if delegate_stake.user == Pubkey::default() {
+        delegate_stake.init(delegate_key, ctx.bumps.delegate_stake);
+    }
+
+    // Delegation is single-hop: no self-delegation, no re-delegating received power.
+    if delegate_key == delegator_key || delegator_stake.delegator_count > 0 || delegate_stake.is_delegated() {
+        return err!(SoreinError::InvalidDelegate);
+    }
+    if delegator_stake.is_delegated() {
+        return err!(SoreinError::AlreadyDelegated);
+    }
+    if delegator_stake.staked_amount == 0 || delegator_stake.staked_amount < ctx.accounts.platform_config.min_stake_amount {
+        return err!(SoreinError::InsufficientDelegatedStake);
+    }
+    if delegate_stake.delegator_count >= MAX_DELEGATORS {
+        return err!(SoreinError::TooManyDelegators);
+    }
+
+    delegator_stake.delegate = delegate_key;
+    delegator_stake.delegation_updated_at = clock.unix_timestamp;
+    delegate_stake.delegator_count += 1;
+
+    // Emit an event for the delegation.
+    emit!(VotesDelegated {
+        delegator: delegator_key,
+        delegate: delegate_key,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for revoking a delegation.
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    /// The user revoking their delegation.
+    pub delegator: Signer<'info>,
+    /// The delegator's stake account.
+    #[account(
+        mut,
+        seeds = [b"user-stake", delegator.key().as_ref()],
+        bump = delegator_stake.bump,
+        constraint = delegator_stake.user == delegator.key() @ SoreinError::UnauthorizedUser
+    )]
+    pub delegator_stake: Account<'info, UserStake>,
+    /// The current delegate's stake account.
+    #[account(
+        mut,
+        seeds = [b"user-stake", delegator_stake.delegate.as_ref()],
+        bump = delegate_stake.bump
+    )]
+    pub delegate_stake: Account<'info, UserStake>,
+}
+
+/// Instruction to revoke a delegation. The delegator can vote directly again on proposals that start afterwards.
+pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+    let clock = Clock::get()?;
+    let delegator_stake = &mut ctx.accounts.delegator_stake;
+    let delegate_stake = &mut ctx.accounts.delegate_stake;
+
+    if !delegator_stake.is_delegated() {
+        return err!(SoreinError::InvalidDelegate);
+    }
+    let delegate = delegator_stake.delegate;
+
+    delegator_stake.delegate = Pubkey::default();
+    delegator_stake.delegation_updated_at = clock.unix_timestamp;
+    delegate_stake.delegator_count = delegate_stake.delegator_count.saturating_sub(1);
+
+    // Emit an event for the revocation.
+    emit!(VotesUndelegated {
+        delegator: ctx.accounts.delegator.key(),
+        delegate,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for a delegate dropping one of their delegators.
+#[derive(Accounts)]
+pub struct RemoveDelegator<'info> {
+    /// The delegate giving up the delegated voting power.
+    pub delegate: Signer<'info>,
+    /// The delegate's stake account.
+    #[account(
+        mut,
+        seeds = [b"user-stake", delegate.key().as_ref()],
+        bump = delegate_stake.bump,
+        constraint = delegate_stake.user == delegate.key() @ SoreinError::UnauthorizedUser
+    )]
+    pub delegate_stake: Account<'info, UserStake>,
+    /// The stake account of the delegator being removed.
+    #[account(
+        mut,
+        seeds = [b"user-stake", delegator_stake.user.as_ref()],
+        bump = delegator_stake.bump,
+        constraint = delegator_stake.delegate == delegate.key() @ SoreinError::InvalidDelegatorAccount
+    )]
+    pub delegator_stake: Account<'info, UserStake>,
+}
+
+/// Instruction for a delegate to revoke a delegation made to them, freeing the slot. The delegator
+/// can vote directly again on proposals that start afterwards.
+pub fn remove_delegator(ctx: Context<RemoveDelegator>) -> Result<()> {
+    let clock = Clock::get()?;
+    let delegate_stake = &mut ctx.accounts.delegate_stake;
+    let delegator_stake = &mut ctx.accounts.delegator_stake;
+
+    delegator_stake.delegate = Pubkey::default();
+    delegator_stake.delegation_updated_at = clock.unix_timestamp;
+    delegate_stake.delegator_count = delegate_stake.delegator_count.saturating_sub(1);
+
+    // Emit an event for the revocation.
+    emit!(VotesUndelegated {
+        delegator: delegator_stake.user,
+        delegate: ctx.accounts.delegate.key(),
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -0,0 +1,265 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{GovernanceRewardEpoch, PlatformConfig, Proposal, VoteRecord, VoterRewardEpoch};
+use crate::events::{ProposerRewardClaimed, VotingRewardClaimed};
+use crate::error::SoreinError;
+
+/// Context for creating the governance reward vault.
+#[derive(Accounts)]
+pub struct InitializeGovernanceRewardVault<'info> {
+    /// The account paying for the vault's rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The platform configuration account, which owns the vault.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The reward mint.
+    #[account(address = platform_config.reward_mint @ SoreinError::InvalidAccount, mint::token_program = token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    /// The vault governance rewards are paid from, kept apart from the staking reward vault.
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"gov-reward-vault", reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub governance_reward_vault: InterfaceAccount<'info, TokenAccount>,
+    /// The token program owning the reward mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to create the vault governance rewards are paid from. It is funded by plain token transfers,
+/// so governance rewards can never draw on the tokens owed to stakers.
+pub fn initialize_governance_reward_vault(_ctx: Context<InitializeGovernanceRewardVault>) -> Result<()> {
+    Ok(())
+}
+
+/// Context for claiming a voter's share of an epoch's voting reward budget.
+#[derive(Accounts)]
+pub struct ClaimVotingReward<'info> {
+    /// The voter claiming the reward.
+    pub voter: Signer<'info>,
+    /// The platform configuration account, which owns the governance reward vault.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The proposal the presented vote was cast on.
+    #[account(address = vote_record.proposal @ SoreinError::InvalidProposal)]
+    pub proposal: Account<'info, Proposal>,
+    /// The voter's record for a vote cast in the epoch.
+    #[account(
+        seeds = [b"vote-record", vote_record.proposal.as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        has_one = voter @ SoreinError::UnauthorizedUser
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    /// The reward epoch the vote counts towards.
+    #[account(
+        mut,
+        seeds = [b"gov-reward-epoch", vote_record.reward_epoch.to_le_bytes().as_ref()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, GovernanceRewardEpoch>,
+    /// The voter's participation in the reward epoch.
+    #[account(
+        mut,
+        seeds = [b"voter-reward-epoch", vote_record.reward_epoch.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = voter_reward_epoch.bump
+    )]
+    pub voter_reward_epoch: Account<'info, VoterRewardEpoch>,
+    /// The reward mint.
+    #[account(address = platform_config.reward_mint @ SoreinError::InvalidAccount, mint::token_program = token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    /// The voter's token account the reward is paid to.
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = voter,
+        token::token_program = token_program
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The governance reward vault the reward is drawn from.
+    #[account(
+        mut,
+        seeds = [b"gov-reward-vault", reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub governance_reward_vault: InterfaceAccount<'info, TokenAccount>,
+    /// The token program owning the reward mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Instruction to claim a voter's pro-rata share of its epoch's voting reward budget, once per epoch.
+/// The share is based on the voter's largest vote in the epoch, so splitting stake across many proposals
+/// earns nothing extra. Claims open once the epoch has ended, and the presented vote must be on a proposal
+/// that reached a final outcome without being flagged as spam or cancelled by the guardian.
+pub fn claim_voting_reward(ctx: Context<ClaimVotingReward>) -> Result<()> {
+    let clock = Clock::get()?;
+    if !ctx.accounts.proposal.is_reward_eligible() {
+        return err!(SoreinError::ProposalNotRewardEligible);
+    }
+    let voter_reward_epoch = &mut ctx.accounts.voter_reward_epoch;
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+    if clock.unix_timestamp < reward_epoch.end_time() {
+        return err!(SoreinError::RewardEpochNotEnded);
+    }
+    if voter_reward_epoch.reward_claimed {
+        return err!(SoreinError::RewardAlreadyClaimed);
+    }
+
+    let reward = reward_epoch.reward_for(voter_reward_epoch.weight)?;
+    if reward == 0 {
+        return err!(SoreinError::NoRewardsAvailable);
+    }
+    voter_reward_epoch.reward_claimed = true;
+    reward_epoch.claimed = reward_epoch.claimed.checked_add(reward).ok_or(SoreinError::ArithmeticError)?;
+    let epoch = reward_epoch.epoch;
+
+    pay_from_governance_reward_vault(
+        &ctx.accounts.platform_config,
+        &ctx.accounts.governance_reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.token_program,
+        reward,
+    )?;
+
+    emit!(VotingRewardClaimed {
+        voter: ctx.accounts.voter.key(),
+        proposal: ctx.accounts.vote_record.proposal,
+        epoch,
+        amount: reward,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Context for claiming the reward for a passed proposal.
+#[derive(Accounts)]
+pub struct ClaimProposerReward<'info> {
+    /// The proposal creator claiming the reward.
+    pub creator: Signer<'info>,
+    /// The platform configuration account, which owns the governance reward vault.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The passed proposal.
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = creator @ SoreinError::UnauthorizedUser
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// The reward epoch the proposal closed in, which caps the proposer rewards paid for it.
+    #[account(
+        mut,
+        seeds = [b"gov-reward-epoch", proposal.reward_epoch().to_le_bytes().as_ref()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, GovernanceRewardEpoch>,
+    /// The reward mint.
+    #[account(address = platform_config.reward_mint @ SoreinError::InvalidAccount, mint::token_program = token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    /// The creator's token account the reward is paid to.
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = creator,
+        token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The governance reward vault the reward is drawn from.
+    #[account(
+        mut,
+        seeds = [b"gov-reward-vault", reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub governance_reward_vault: InterfaceAccount<'info, TokenAccount>,
+    /// The token program owning the reward mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Instruction for the creator of a passed proposal to claim the proposer reward, once per proposal.
+/// The reward is the one in force when the proposal was signed off, only proposals that do something
+/// on execution earn it, and the total paid per epoch is capped.
+pub fn claim_proposer_reward(ctx: Context<ClaimProposerReward>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    if !proposal.has_passed() {
+        return err!(SoreinError::ProposalNotPassed);
+    }
+    if !proposal.has_payload() || !proposal.is_reward_eligible() {
+        return err!(SoreinError::ProposalNotRewardEligible);
+    }
+    if proposal.proposer_reward_claimed {
+        return err!(SoreinError::RewardAlreadyClaimed);
+    }
+    let reward = proposal.proposer_reward;
+    if reward == 0 {
+        return err!(SoreinError::NoRewardsAvailable);
+    }
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+    let paid = reward_epoch.proposer_rewards_paid.checked_add(reward).ok_or(SoreinError::ArithmeticError)?;
+    if paid > reward_epoch.proposer_reward_cap {
+        return err!(SoreinError::ProposerRewardCapReached);
+    }
+    reward_epoch.proposer_rewards_paid = paid;
+    proposal.proposer_reward_claimed = true;
+
+    pay_from_governance_reward_vault(
+        &ctx.accounts.platform_config,
+        &ctx.accounts.governance_reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.creator_token_account,
+        &ctx.accounts.token_program,
+        reward,
+    )?;
+
+    let clock = Clock::get()?;
+    emit!(ProposerRewardClaimed {
+        proposal_id,
+        creator: ctx.accounts.creator.key(),
+        amount: reward,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Transfers `amount` from the governance reward vault, signed by the platform config PDA.
+fn pay_from_governance_reward_vault<'info>(
+    platform_config: &Account<'info, PlatformConfig>,
+    governance_reward_vault: &InterfaceAccount<'info, TokenAccount>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    recipient: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if governance_reward_vault.amount < amount {
+        return err!(SoreinError::InsufficientGovernanceRewardFunds);
+    }
+    let bump = [platform_config.bump];
+    let seeds: &[&[u8]] = &[b"platform-config", &bump];
+    let signer = &[seeds];
+    let cpi_accounts = TransferChecked {
+        from: governance_reward_vault.to_account_info(),
+        mint: reward_mint.to_account_info(),
+        to: recipient.to_account_info(),
+        authority: platform_config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, reward_mint.decimals)
+}
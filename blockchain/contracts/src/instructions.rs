@@ -1,7 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3Cpi, CreateMasterEditionV3CpiAccounts, CreateMasterEditionV3InstructionArgs,
+    CreateMetadataAccountV3Cpi, CreateMetadataAccountV3CpiAccounts, CreateMetadataAccountV3InstructionArgs,
+};
+use mpl_token_metadata::types::DataV2;
+use crate::error::SoreinError;
+use crate::events::{
+    AgentClosed, AgentOwnerProposed, AgentOwnerTransferred, AgentRegistered, AgentStatusChanged, AgentTokenized,
+    AgentUpdated,
+};
+use crate::math;
 use crate::state::*;
-use crate::ErrorCode;
 
 // Initialize the platform configuration
 #[derive(Accounts)]
@@ -14,9 +26,40 @@ pub struct InitializePlatform<'info> {
         bump
     )]
     pub platform_config: Account<'info, PlatformConfig>,
+    #[account(mint::token_program = stake_token_program)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = reward_token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    // Program-owned vault holding staked tokens
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"staking-vault", stake_mint.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = platform_config,
+        token::token_program = stake_token_program
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+    // Program-owned vault holding tokens paid out as rewards
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"reward-vault", reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = platform_config,
+        token::token_program = reward_token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub admin: Signer<'info>,
+    // Token program owning the stake mint (SPL Token or Token-2022)
+    pub stake_token_program: Interface<'info, TokenInterface>,
+    // Token program owning the reward mint (SPL Token or Token-2022)
+    pub reward_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn initialize_platform(
@@ -24,17 +67,26 @@ pub fn initialize_platform(
     reward_rate_bps: u64,
     min_stake_amount: u64,
     epoch_duration: i64,
+    unstake_cooldown: i64,
 ) -> Result<()> {
     let platform_config = &mut ctx.accounts.platform_config;
     let bump = ctx.bumps.platform_config;
 
+    require!(unstake_cooldown >= 0, SoreinError::InvalidCooldown);
+
     platform_config.init(
         ctx.accounts.admin.key(),
         reward_rate_bps,
         min_stake_amount,
         epoch_duration,
+        unstake_cooldown,
         bump,
     );
+    platform_config.last_reward_timestamp = Clock::get()?.unix_timestamp;
+    platform_config.stake_mint = ctx.accounts.stake_mint.key();
+    platform_config.reward_mint = ctx.accounts.reward_mint.key();
+    platform_config.staking_vault = ctx.accounts.staking_vault.key();
+    platform_config.reward_vault = ctx.accounts.reward_vault.key();
 
     msg!("Platform initialized with admin: {}", ctx.accounts.admin.key());
     Ok(())
@@ -47,7 +99,9 @@ pub struct UpdatePlatformConfig<'info> {
         mut,
         seeds = [b"platform-config"],
         bump = platform_config.bump,
-        has_one = admin @ ErrorCode::Unauthorized
+        has_one = admin @ ErrorCode::Unauthorized,
+        // Once governance is enabled, config changes go through execute_proposal instead
+        constraint = !platform_config.governance_enabled @ SoreinError::GovernanceControlled
     )]
     pub platform_config: Account<'info, PlatformConfig>,
     #[account(mut)]
@@ -59,74 +113,368 @@ pub fn update_platform_config(
     reward_rate_bps: u64,
     min_stake_amount: u64,
     epoch_duration: i64,
+    unstake_cooldown: i64,
 ) -> Result<()> {
     let platform_config = &mut ctx.accounts.platform_config;
 
+    require!(unstake_cooldown >= 0, SoreinError::InvalidCooldown);
+
+    // Accrue rewards at the old rate before the new parameters take effect
+    platform_config.update_rewards(Clock::get()?.unix_timestamp)?;
+
     platform_config.reward_rate_bps = reward_rate_bps;
     platform_config.min_stake_amount = min_stake_amount;
     platform_config.epoch_duration = epoch_duration;
+    platform_config.unstake_cooldown = unstake_cooldown;
 
     msg!("Platform config updated by admin: {}", ctx.accounts.admin.key());
     Ok(())
 }
 
-// Register a new AI agent
+// Configure how lockups translate into voting power (admin only)
+pub fn configure_lockup(
+    ctx: Context<UpdatePlatformConfig>,
+    lockup_mode: LockupMode,
+    min_lockup_period: i64,
+    max_lockup_period: i64,
+    max_lockup_multiplier_bps: u64,
+    lockup_tiers: Vec<LockupTier>,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    validate_lockup(min_lockup_period, max_lockup_period, max_lockup_multiplier_bps, &lockup_tiers)?;
+
+    // Unused tier slots are left zeroed
+    let mut tiers = [LockupTier::default(); MAX_LOCKUP_TIERS];
+    tiers[..lockup_tiers.len()].copy_from_slice(&lockup_tiers);
+
+    platform_config.lockup_mode = lockup_mode;
+    platform_config.min_lockup_period = min_lockup_period;
+    platform_config.max_lockup_period = max_lockup_period;
+    platform_config.max_lockup_multiplier_bps = max_lockup_multiplier_bps;
+    platform_config.lockup_tiers = tiers;
+
+    msg!("Lockup config updated by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Set the thresholds applied to proposals without a type-specific override (admin only)
+pub fn set_default_thresholds(ctx: Context<UpdatePlatformConfig>, thresholds: GovernanceThresholds) -> Result<()> {
+    validate_thresholds(&thresholds)?;
+    ctx.accounts.platform_config.default_thresholds = thresholds;
+
+    msg!("Default governance thresholds updated by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Set or clear the threshold override for one proposal type (admin only)
+pub fn set_threshold_override(
+    ctx: Context<UpdatePlatformConfig>,
+    proposal_type: ProposalType,
+    thresholds: Option<GovernanceThresholds>,
+) -> Result<()> {
+    if let Some(thresholds) = thresholds.as_ref() {
+        validate_thresholds(thresholds)?;
+    }
+    ctx.accounts.platform_config.threshold_overrides[proposal_type.index()] = thresholds;
+
+    msg!("Threshold override for {:?} updated by admin: {}", proposal_type, ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Lockup bounds must be ordered and every tier in use must fall within them;
+// also checked when a ConfigureLockup proposal action is applied
+pub(crate) fn validate_lockup(
+    min_lockup_period: i64,
+    max_lockup_period: i64,
+    max_lockup_multiplier_bps: u64,
+    lockup_tiers: &[LockupTier],
+) -> Result<()> {
+    require!(
+        min_lockup_period > 0 && min_lockup_period <= max_lockup_period,
+        SoreinError::InvalidLockupPeriod
+    );
+    require!(max_lockup_multiplier_bps >= math::BASE_MULTIPLIER_BPS, SoreinError::InvalidLockupMultiplier);
+    require!(lockup_tiers.len() <= MAX_LOCKUP_TIERS, SoreinError::InvalidLockupMultiplier);
+    for tier in lockup_tiers.iter() {
+        require!(
+            tier.period > 0 && tier.period <= max_lockup_period,
+            SoreinError::InvalidLockupPeriod
+        );
+        require!(tier.multiplier_bps >= math::BASE_MULTIPLIER_BPS, SoreinError::InvalidLockupMultiplier);
+    }
+    Ok(())
+}
+
+// Thresholds are basis points; a proposal must always need some approval to pass
+pub(crate) fn validate_thresholds(thresholds: &GovernanceThresholds) -> Result<()> {
+    let max_bps = math::BPS_DENOMINATOR as u64;
+    require!(
+        thresholds.approval_bps > 0 && thresholds.approval_bps <= max_bps,
+        SoreinError::InvalidThreshold
+    );
+    require!(thresholds.quorum_bps <= max_bps, SoreinError::InvalidThreshold);
+    require!(thresholds.veto_bps <= max_bps, SoreinError::InvalidThreshold);
+    Ok(())
+}
+
+// Configure the timelock between proposal approval and execution (admin only)
+pub fn configure_timelock(
+    ctx: Context<UpdatePlatformConfig>,
+    execution_delay: i64,
+    critical_execution_delay: i64,
+    execution_grace_period: i64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    validate_timelock(execution_delay, critical_execution_delay, execution_grace_period)?;
+
+    platform_config.execution_delay = execution_delay;
+    platform_config.critical_execution_delay = critical_execution_delay;
+    platform_config.execution_grace_period = execution_grace_period;
+
+    msg!("Timelock config updated by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Critical proposals must never wait less than regular ones, and approved ones must stay executable for a while
+pub(crate) fn validate_timelock(execution_delay: i64, critical_execution_delay: i64, execution_grace_period: i64) -> Result<()> {
+    require!(
+        execution_delay >= 0 && critical_execution_delay >= execution_delay,
+        SoreinError::InvalidTimelock
+    );
+    require!(execution_grace_period > 0, SoreinError::InvalidTimelock);
+    Ok(())
+}
+
+// Update the proposal deposit, the minimum stake needed to propose and the voting period bounds (admin only)
+pub fn configure_proposal_requirements(
+    ctx: Context<UpdatePlatformConfig>,
+    proposal_deposit: u64,
+    min_stake_to_propose: u64,
+    min_voting_duration: u64,
+    max_voting_duration: u64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    require!(
+        min_voting_duration > 0 && min_voting_duration <= max_voting_duration,
+        SoreinError::InvalidVotingDuration
+    );
+
+    platform_config.proposal_deposit = proposal_deposit;
+    platform_config.min_stake_to_propose = min_stake_to_propose;
+    platform_config.min_voting_duration = min_voting_duration;
+    platform_config.max_voting_duration = max_voting_duration;
+
+    msg!("Proposal requirements updated by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Update the per-epoch voting reward budget, the passed-proposal reward and the per-epoch
+// proposer reward cap (admin only)
+pub fn configure_governance_rewards(
+    ctx: Context<UpdatePlatformConfig>,
+    voting_reward_budget: u64,
+    proposer_reward: u64,
+    proposer_reward_epoch_cap: u64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.voting_reward_budget = voting_reward_budget;
+    platform_config.proposer_reward = proposer_reward;
+    platform_config.proposer_reward_epoch_cap = proposer_reward_epoch_cap;
+
+    msg!("Governance rewards updated by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Set the guardian allowed to cancel proposals before execution (admin only)
+pub fn set_guardian(ctx: Context<UpdatePlatformConfig>, guardian: Pubkey) -> Result<()> {
+    ctx.accounts.platform_config.guardian = guardian;
+
+    msg!("Guardian set to {} by admin: {}", guardian, ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Enable or disable governance proposals and voting (admin only).
+// Once enabled, only a ToggleGovernance proposal can turn it off again.
+pub fn set_governance_enabled(ctx: Context<UpdatePlatformConfig>, enabled: bool) -> Result<()> {
+    ctx.accounts.platform_config.governance_enabled = enabled;
+
+    msg!("Governance enabled set to {} by admin: {}", enabled, ctx.accounts.admin.key());
+    Ok(())
+}
+
+// Register a new AI agent under the next ID from the platform counter
 #[derive(Accounts)]
 pub struct RegisterAiAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
     #[account(
         init,
         payer = owner,
         space = AiAgent::SPACE,
-        seeds = [b"ai-agent", owner.key().as_ref(), &agent_id.to_le_bytes()],
+        seeds = [b"ai-agent".as_ref(), &platform_config.agent_count.to_le_bytes()],
         bump
     )]
     pub ai_agent: Account<'info, AiAgent>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = OwnerAgents::SPACE,
+        seeds = [b"owner-agents", owner.key().as_ref()],
+        bump
+    )]
+    pub owner_agents: Account<'info, OwnerAgents>,
     #[account(mut)]
     pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
+    // The NFT accounts below are passed to tokenize the agent at registration, and omitted otherwise
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"agent-mint".as_ref(), &platform_config.agent_count.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = platform_config,
+        mint::freeze_authority = platform_config,
+        mint::token_program = token_program
+    )]
+    pub nft_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_nft_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: metadata PDA of nft_mint, created and verified by the token metadata program
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: master edition PDA of nft_mint, created and verified by the token metadata program
+    #[account(mut)]
+    pub master_edition: Option<UncheckedAccount<'info>>,
+    // SPL Token only, as in TokenizeAiAgent
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    /// CHECK: address is checked against the token metadata program ID
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
 }
 
+impl<'info> RegisterAiAgent<'info> {
+    // The accounts minting the agent's NFT, if every one of them was passed (the associated token
+    // program only creates owner_nft_account, so it isn't carried further)
+    fn nft_accounts(&self) -> Option<AgentNftAccounts<'info>> {
+        self.associated_token_program.as_ref()?;
+        Some(AgentNftAccounts {
+            platform_config: self.platform_config.to_account_info(),
+            nft_mint: self.nft_mint.as_ref()?.to_account_info(),
+            owner_nft_account: self.owner_nft_account.as_ref()?.to_account_info(),
+            metadata: self.metadata.as_ref()?.to_account_info(),
+            master_edition: self.master_edition.as_ref()?.to_account_info(),
+            owner: self.owner.to_account_info(),
+            token_program: self.token_program.as_ref()?.to_account_info(),
+            token_metadata_program: self.token_metadata_program.as_ref()?.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        })
+    }
+}
+
+// Register an agent, optionally minting its NFT in the same instruction when `uri` is given
 pub fn register_ai_agent(
     ctx: Context<RegisterAiAgent>,
-    agent_id: u64,
     name: String,
     description: String,
+    uri: Option<String>,
 ) -> Result<()> {
+    // Tokenizing at registration takes a URI and every NFT account; without a URI no NFT mint may be passed
+    let nft_accounts = ctx.accounts.nft_accounts();
+    if uri.is_some() != nft_accounts.is_some() || (uri.is_none() && ctx.accounts.nft_mint.is_some()) {
+        return err!(SoreinError::InvalidAccount);
+    }
+
+    let platform_config = &mut ctx.accounts.platform_config;
     let ai_agent = &mut ctx.accounts.ai_agent;
+    let owner_agents = &mut ctx.accounts.owner_agents;
     let bump = ctx.bumps.ai_agent;
     let clock = Clock::get()?;
 
     // Validate input lengths
     require!(name.len() <= MAX_NAME_LENGTH, ErrorCode::MetadataTooLarge);
     require!(description.len() <= MAX_DESCRIPTION_LENGTH, ErrorCode::MetadataTooLarge);
+    if let Some(uri) = uri.as_ref() {
+        require!(uri.len() <= MAX_URI_LENGTH, SoreinError::MetadataTooLarge);
+    }
 
+    // Enforce the per-owner agent limit
+    if owner_agents.owner == Pubkey::default() {
+        owner_agents.init(ctx.accounts.owner.key(), ctx.bumps.owner_agents);
+    }
+    owner_agents.add_agent()?;
+
+    // Take the next agent ID
+    let agent_id = platform_config.agent_count;
+    platform_config.agent_count = agent_id.checked_add(1).ok_or(SoreinError::ArithmeticError)?;
+
+    // Start the agent's reward accounting from the current global accumulator
+    platform_config.update_rewards(clock.unix_timestamp)?;
+
+    let metadata = format!("{}: {}", name, description);
     ai_agent.init(
         agent_id,
         ctx.accounts.owner.key(),
         name,
         description,
         clock.unix_timestamp,
+        platform_config.reward_per_share,
         bump,
     );
 
-    msg!("AI Agent registered: ID {} by owner {}", agent_id, ctx.accounts.owner.key());
+    emit!(AgentRegistered {
+        agent_id,
+        owner: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+        metadata,
+    });
+
+    if let (Some(uri), Some(nft_accounts)) = (uri, nft_accounts) {
+        nft_accounts.mint(platform_config.bump, ai_agent.name.clone(), uri.clone())?;
+        ai_agent.nft_mint = nft_accounts.nft_mint.key();
+        ai_agent.uri = uri;
+
+        emit!(AgentTokenized {
+            agent_id,
+            owner: ai_agent.owner,
+            nft_mint: ai_agent.nft_mint,
+            uri: ai_agent.uri.clone(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
     Ok(())
 }
 
 // Stake tokens on an AI agent
 #[derive(Accounts)]
+#[instruction(agent_id: u64)]
 pub struct StakeOnAgent<'info> {
     #[account(
         mut,
         seeds = [b"platform-config"],
-        bump = platform_config.bump
+        bump = platform_config.bump,
+        // Staking is halted while the council has the platform paused
+        constraint = !platform_config.paused @ SoreinError::PlatformPaused
     )]
     pub platform_config: Account<'info, PlatformConfig>,
     #[account(
         mut,
-        seeds = [b"ai-agent", user.key().as_ref(), &agent_id.to_le_bytes()],
-        bump = ai_agent.bump,
-        has_one = owner @ ErrorCode::Unauthorized
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump
     )]
     pub ai_agent: Account<'info, AiAgent>,
     #[account(
@@ -137,13 +485,45 @@ pub struct StakeOnAgent<'info> {
         bump
     )]
     pub user_stake: Account<'info, UserStake>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakePosition::SPACE,
+        seeds = [b"stake-position", user.key().as_ref(), &agent_id.to_le_bytes()],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakeCheckpoints::SPACE,
+        seeds = [b"stake-checkpoints", user.key().as_ref()],
+        bump
+    )]
+    pub stake_checkpoints: Account<'info, StakeCheckpoints>,
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub platform_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        address = platform_config.stake_mint,
+        mint::token_program = token_program
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = platform_config.staking_vault @ SoreinError::InvalidVault,
+        token::mint = stake_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -152,53 +532,91 @@ pub fn stake_on_agent(
     agent_id: u64,
     amount: u64,
 ) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(amount > 0, ErrorCode::InvalidStakeAmount);
+    require!(ctx.accounts.ai_agent.status == AgentStatus::Active, SoreinError::AgentNotActive);
+
+    // Transfer tokens from user to the staking vault
+    let vault_balance_before = ctx.accounts.staking_vault.amount;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        mint: ctx.accounts.stake_mint.to_account_info(),
+        to: ctx.accounts.staking_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.stake_mint.decimals)?;
+
+    // Credit only what the vault actually received (Token-2022 transfer fees are withheld in transit)
+    ctx.accounts.staking_vault.reload()?;
+    let received = ctx.accounts.staking_vault.amount
+        .checked_sub(vault_balance_before)
+        .ok_or(SoreinError::ArithmeticError)?;
+
     let platform_config = &mut ctx.accounts.platform_config;
     let ai_agent = &mut ctx.accounts.ai_agent;
     let user_stake = &mut ctx.accounts.user_stake;
-    let clock = Clock::get()?;
+    let stake_position = &mut ctx.accounts.stake_position;
 
     // Validate stake amount
-    require!(amount >= platform_config.min_stake_amount, ErrorCode::InvalidStakeAmount);
+    require!(received >= platform_config.min_stake_amount, ErrorCode::InvalidStakeAmount);
 
     // Initialize user stake if newly created
     if user_stake.user == Pubkey::default() {
         user_stake.init(ctx.accounts.user.key(), ctx.bumps.user_stake);
     }
+    let stake_checkpoints = &mut ctx.accounts.stake_checkpoints;
+    if stake_checkpoints.user == Pubkey::default() {
+        stake_checkpoints.init(ctx.accounts.user.key(), ctx.bumps.stake_checkpoints);
+    }
 
-    // Add agent to user's staked agents list
-    user_stake.add_staked_agent(agent_id)?;
+    // Open a new position if this is the user's first stake on the agent
+    if stake_position.user == Pubkey::default() {
+        stake_position.init(ctx.accounts.user.key(), agent_id, clock.unix_timestamp, ctx.bumps.stake_position);
+        user_stake.position_count = user_stake.position_count.checked_add(1).ok_or(SoreinError::ArithmeticError)?;
+        ai_agent.position_count = ai_agent.position_count.checked_add(1).ok_or(SoreinError::ArithmeticError)?;
+    }
+
+    // Settle rewards earned on the existing amount before it changes
+    platform_config.update_rewards(clock.unix_timestamp)?;
+    ai_agent.sync_rewards(platform_config.reward_per_share)?;
+    stake_position.settle_rewards(ai_agent.reward_per_share)?;
 
     // Update stake amounts
-    user_stake.staked_amount = user_stake.staked_amount.checked_add(amount).ok_or(ErrorCode::InvalidStakeAmount)?;
-    ai_agent.staked_amount = ai_agent.staked_amount.checked_add(amount).ok_or(ErrorCode::InvalidStakeAmount)?;
-    platform_config.total_staked = platform_config.total_staked.checked_add(amount).ok_or(ErrorCode::InvalidStakeAmount)?;
+    stake_position.amount = stake_position.amount.checked_add(received).ok_or(SoreinError::ArithmeticError)?;
+    user_stake.staked_amount = user_stake.staked_amount.checked_add(received).ok_or(SoreinError::ArithmeticError)?;
+    ai_agent.staked_amount = ai_agent.staked_amount.checked_add(received).ok_or(SoreinError::ArithmeticError)?;
+    platform_config.total_staked = platform_config.total_staked.checked_add(received).ok_or(SoreinError::ArithmeticError)?;
+    stake_position.checkpoint_rewards(ai_agent.reward_per_share)?;
 
     // Update timestamps
     user_stake.last_stake_update = clock.unix_timestamp;
 
-    // Transfer tokens from user to platform vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.platform_vault.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
+    // Record the new total in the checkpoint history read by proposal snapshots
+    stake_checkpoints.record(clock.slot, user_stake.staked_amount, user_stake.lockup_end);
 
-    msg!("User {} staked {} on agent {}", ctx.accounts.user.key(), amount, agent_id);
+    msg!("User {} staked {} (received {}) on agent {}", ctx.accounts.user.key(), amount, received, agent_id);
     Ok(())
 }
 
-// Claim accumulated rewards
+// Unstake part of a position on an AI agent into the cooldown queue
 #[derive(Accounts)]
-pub struct ClaimRewards<'info> {
+#[instruction(agent_id: u64)]
+pub struct UnstakeFromAgent<'info> {
     #[account(
         mut,
         seeds = [b"platform-config"],
-        bump = platform_config.bump
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ SoreinError::PlatformPaused
     )]
     pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
     #[account(
         mut,
         seeds = [b"user-stake", user.key().as_ref()],
@@ -206,110 +624,883 @@ pub struct ClaimRewards<'info> {
         has_one = user @ ErrorCode::Unauthorized
     )]
     pub user_stake: Account<'info, UserStake>,
+    #[account(
+        mut,
+        seeds = [b"stake-position", user.key().as_ref(), &agent_id.to_le_bytes()],
+        bump = stake_position.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(
+        mut,
+        seeds = [b"stake-checkpoints", user.key().as_ref()],
+        bump = stake_checkpoints.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_checkpoints: Account<'info, StakeCheckpoints>,
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub platform_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
 }
 
-pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+pub fn unstake_from_agent(
+    ctx: Context<UnstakeFromAgent>,
+    agent_id: u64,
+    amount: u64,
+) -> Result<()> {
     let platform_config = &mut ctx.accounts.platform_config;
+    let ai_agent = &mut ctx.accounts.ai_agent;
     let user_stake = &mut ctx.accounts.user_stake;
+    let stake_position = &mut ctx.accounts.stake_position;
     let clock = Clock::get()?;
 
-    // Calculate elapsed epochs since last claim
-    let elapsed_time = clock.unix_timestamp - user_stake.last_reward_claim;
-    let elapsed_epochs = elapsed_time / platform_config.epoch_duration;
-    if elapsed_epochs <= 0 {
-        return err!(ErrorCode::NoRewardsToClaim);
-    }
+    // Validate unstake amount against the user's position
+    require!(amount > 0, ErrorCode::InvalidStakeAmount);
+    require!(amount <= stake_position.amount, SoreinError::InvalidUnstakeAmount);
+    require!(!user_stake.is_locked(clock.unix_timestamp), SoreinError::StakeLocked);
 
-    // Calculate rewards based on staked amount and reward rate
-    let reward_per_epoch = (user_stake.staked_amount as u128)
-        .checked_mul(platform_config.reward_rate_bps as u128)
-        .ok_or(ErrorCode::InvalidStakeAmount)?
-        .checked_div(10000) // Convert basis points to percentage
-        .ok_or(ErrorCode::InvalidStakeAmount)? as u64;
-    let total_reward = reward_per_epoch
-        .checked_mul(elapsed_epochs as u64)
-        .ok_or(ErrorCode::InvalidStakeAmount)?;
-
-    // Update accumulated rewards and reset claim timestamp
-    user_stake.accumulated_rewards = user_stake.accumulated_rewards
-        .checked_add(total_reward)
-        .ok_or(ErrorCode::InvalidStakeAmount)?;
-    let reward_to_claim = user_stake.accumulated_rewards;
-    user_stake.accumulated_rewards = 0;
-    user_stake.last_reward_claim = clock.unix_timestamp;
-
-    // Transfer rewards from platform vault to user
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.platform_vault.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.platform_vault.to_account_info(), // Assumes vault has delegated authority or program owns it
+    // Move the amount out of the active stake and into the withdrawal queue.
+    // Stake on a retired agent skips the cooldown so stakers can leave right away.
+    let unlock_at = if ai_agent.status == AgentStatus::Retired {
+        clock.unix_timestamp
+    } else {
+        clock.unix_timestamp
+            .checked_add(platform_config.unstake_cooldown)
+            .ok_or(SoreinError::InvalidCooldown)?
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, reward_to_claim)?;
+    user_stake.queue_withdrawal(agent_id, amount, unlock_at)?;
+
+    // Settle rewards earned on the existing amount before it changes
+    platform_config.update_rewards(clock.unix_timestamp)?;
+    ai_agent.sync_rewards(platform_config.reward_per_share)?;
+    stake_position.settle_rewards(ai_agent.reward_per_share)?;
+
+    stake_position.amount = stake_position.amount.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+    user_stake.staked_amount = user_stake.staked_amount.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+    ai_agent.staked_amount = ai_agent.staked_amount.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+    platform_config.total_staked = platform_config.total_staked.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+    stake_position.checkpoint_rewards(ai_agent.reward_per_share)?;
 
-    msg!("User {} claimed rewards: {}", ctx.accounts.user.key(), reward_to_claim);
+    user_stake.last_stake_update = clock.unix_timestamp;
+    ctx.accounts.stake_checkpoints.record(clock.slot, user_stake.staked_amount, user_stake.lockup_end);
+
+    msg!("User {} unstaked {} from agent {}, unlocks at {}", ctx.accounts.user.key(), amount, agent_id, unlock_at);
     Ok(())
 }
 
-// Vote on governance proposals (e.g., update reward rates)
+// Lock the user's stake for a chosen period in exchange for extra voting power
 #[derive(Accounts)]
-pub struct VoteOnProposal<'info> {
+pub struct LockStake<'info> {
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ SoreinError::PlatformPaused
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"user-stake", user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserStake>,
     #[account(
         mut,
+        seeds = [b"stake-checkpoints", user.key().as_ref()],
+        bump = stake_checkpoints.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_checkpoints: Account<'info, StakeCheckpoints>,
+    pub user: Signer<'info>,
+}
+
+pub fn lock_stake(ctx: Context<LockStake>, lockup_period: i64) -> Result<()> {
+    let platform_config = &ctx.accounts.platform_config;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let clock = Clock::get()?;
+
+    // Validate the lock period against the platform bounds
+    require!(
+        lockup_period >= platform_config.min_lockup_period && lockup_period <= platform_config.max_lockup_period,
+        SoreinError::InvalidLockupPeriod
+    );
+    require!(user_stake.staked_amount > 0, ErrorCode::InvalidStakeAmount);
+
+    user_stake.lock(clock.unix_timestamp, lockup_period)?;
+    ctx.accounts.stake_checkpoints.record(clock.slot, user_stake.staked_amount, user_stake.lockup_end);
+
+    msg!("User {} locked stake until {}", ctx.accounts.user.key(), user_stake.lockup_end);
+    Ok(())
+}
+
+// Withdraw all queued unstakes whose cooldown has elapsed
+#[derive(Accounts)]
+pub struct WithdrawUnlocked<'info> {
+    #[account(
         seeds = [b"platform-config"],
-        bump = platform_config.bump
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ SoreinError::PlatformPaused
     )]
     pub platform_config: Account<'info, PlatformConfig>,
     #[account(
         mut,
-        seeds = [b"user-stake", voter.key().as_ref()],
+        seeds = [b"user-stake", user.key().as_ref()],
         bump = user_stake.bump,
-        has_one = voter @ ErrorCode::Unauthorized
+        has_one = user @ ErrorCode::Unauthorized
     )]
     pub user_stake: Account<'info, UserStake>,
     #[account(mut)]
-    pub voter: Signer<'info>,
+    pub user: Signer<'info>,
     #[account(
-        init_if_needed,
-        payer = voter,
-        space = Metadata::SPACE,
-        seeds = [b"proposal-vote", &proposal_id.to_le_bytes(), voter.key().as_ref()],
-        bump
+        address = platform_config.stake_mint,
+        mint::token_program = token_program
     )]
-    pub vote_record: Account<'info, Metadata>,
-    pub system_program: Program<'info, System>,
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = platform_config.staking_vault @ SoreinError::InvalidVault,
+        token::mint = stake_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn vote_on_proposal(
-    ctx: Context<VoteOnProposal>,
-    proposal_id: u64,
-    in_favor: bool,
-) -> Result<()> {
-    let user_stake = &ctx.accounts.user_stake;
-    let vote_record = &mut ctx.accounts.vote_record;
+pub fn withdraw_unlocked(ctx: Context<WithdrawUnlocked>) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake;
     let clock = Clock::get()?;
 
-    // Ensure user has staked tokens to have voting power
-    require!(user_stake.staked_amount > 0, ErrorCode::InvalidStakeAmount);
+    let amount = user_stake.take_unlocked_withdrawals(clock.unix_timestamp)?;
+    require!(amount > 0, SoreinError::NoUnlockedWithdrawals);
 
-    // Record the vote (simplified as metadata)
-    let vote_data = format!("Vote: {}", if in_favor { "Yes" } else { "No" });
-    vote_record.init(
-        proposal_id,
-        vote_data,
-        clock.unix_timestamp,
-        ctx.bumps.vote_record,
-    );
+    // Transfer matured withdrawals from the staking vault to user, signed by the platform config PDA
+    let bump = [ctx.accounts.platform_config.bump];
+    let seeds: &[&[u8]] = &[b"platform-config", &bump];
+    let signer = &[seeds];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.staking_vault.to_account_info(),
+        mint: ctx.accounts.stake_mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.platform_config.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.stake_mint.decimals)?;
+
+    msg!("User {} withdrew {} unlocked tokens", ctx.accounts.user.key(), amount);
+    Ok(())
+}
 
-    msg!("User {} voted on proposal {}: {}", ctx.accounts.voter.key(), proposal_id, in_favor);
+// Claim accumulated rewards for a single agent position
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ SoreinError::PlatformPaused
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    #[account(
+        mut,
+        seeds = [b"stake-position", user.key().as_ref(), &agent_id.to_le_bytes()],
+        bump = stake_position.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        address = platform_config.reward_mint,
+        mint::token_program = token_program
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = platform_config.reward_vault @ SoreinError::InvalidVault,
+        token::mint = reward_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn claim_rewards(ctx: Context<ClaimRewards>, agent_id: u64) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let stake_position = &mut ctx.accounts.stake_position;
+    let clock = Clock::get()?;
+
+    // Bring the global and agent accumulators up to date and settle the position
+    platform_config.update_rewards(clock.unix_timestamp)?;
+    ai_agent.sync_rewards(platform_config.reward_per_share)?;
+    stake_position.settle_rewards(ai_agent.reward_per_share)?;
+
+    let reward_to_claim = stake_position.accumulated_rewards;
+    if reward_to_claim == 0 {
+        return err!(ErrorCode::NoRewardsToClaim);
+    }
+    stake_position.accumulated_rewards = 0;
+
+    // Transfer rewards from the reward vault to user, signed by the platform config PDA
+    let bump = [ctx.accounts.platform_config.bump];
+    let seeds: &[&[u8]] = &[b"platform-config", &bump];
+    let signer = &[seeds];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.platform_config.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, reward_to_claim, ctx.accounts.reward_mint.decimals)?;
+
+    msg!("User {} claimed rewards from agent {}: {}", ctx.accounts.user.key(), agent_id, reward_to_claim);
+    Ok(())
+}
+
+// Close an emptied stake position and reclaim its rent
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct CloseStakePosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    #[account(
+        mut,
+        seeds = [b"user-stake", user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserStake>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"stake-position", user.key().as_ref(), &agent_id.to_le_bytes()],
+        bump = stake_position.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn close_stake_position(ctx: Context<CloseStakePosition>, agent_id: u64) -> Result<()> {
+    let stake_position = &ctx.accounts.stake_position;
+
+    // Everything must be unstaked and every reward claimed first
+    require!(
+        stake_position.amount == 0 && stake_position.accumulated_rewards == 0,
+        SoreinError::PositionNotEmpty
+    );
+
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let user_stake = &mut ctx.accounts.user_stake;
+    ai_agent.position_count = ai_agent.position_count.checked_sub(1).ok_or(SoreinError::ArithmeticError)?;
+    user_stake.position_count = user_stake.position_count.checked_sub(1).ok_or(SoreinError::ArithmeticError)?;
+
+    msg!("User {} closed position on agent {}", ctx.accounts.user.key(), agent_id);
+    Ok(())
+}
+
+// Update, pause, resume or retire an AI agent (owner only)
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct ManageAiAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump,
+        constraint = ai_agent.is_owner(&owner.key(), owner_nft_account.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    pub owner: Signer<'info>,
+    // The owner's token account holding the agent NFT, required once the agent is tokenized
+    pub owner_nft_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub fn update_agent_metadata(
+    ctx: Context<ManageAiAgent>,
+    agent_id: u64,
+    name: String,
+    description: String,
+) -> Result<()> {
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let clock = Clock::get()?;
+
+    // Validate input lengths
+    require!(name.len() <= MAX_NAME_LENGTH, SoreinError::MetadataTooLarge);
+    require!(description.len() <= MAX_DESCRIPTION_LENGTH, SoreinError::MetadataTooLarge);
+    require!(ai_agent.status != AgentStatus::Retired, SoreinError::InvalidAgentStatus);
+
+    ai_agent.name = name;
+    ai_agent.description = description;
+
+    // Report the acting owner, who for a tokenized agent may not be the recorded one yet
+    emit!(AgentUpdated {
+        agent_id,
+        owner: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+        new_metadata: format!("{}: {}", ai_agent.name, ai_agent.description),
+    });
+    Ok(())
+}
+
+pub fn pause_agent(ctx: Context<ManageAiAgent>, agent_id: u64) -> Result<()> {
+    set_agent_status(ctx, agent_id, AgentStatus::Active, AgentStatus::Paused)
+}
+
+pub fn resume_agent(ctx: Context<ManageAiAgent>, agent_id: u64) -> Result<()> {
+    set_agent_status(ctx, agent_id, AgentStatus::Paused, AgentStatus::Active)
+}
+
+pub fn retire_agent(ctx: Context<ManageAiAgent>, agent_id: u64) -> Result<()> {
+    let from = ctx.accounts.ai_agent.status;
+    require!(from != AgentStatus::Retired, SoreinError::InvalidAgentStatus);
+    set_agent_status(ctx, agent_id, from, AgentStatus::Retired)
+}
+
+// Move an agent from one status to another, settling its rewards at the boundary
+fn set_agent_status(ctx: Context<ManageAiAgent>, agent_id: u64, from: AgentStatus, to: AgentStatus) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let clock = Clock::get()?;
+
+    require!(ai_agent.status == from, SoreinError::InvalidAgentStatus);
+
+    // Sync under the old status so accrual stops (or restarts) exactly at this point
+    platform_config.update_rewards(clock.unix_timestamp)?;
+    ai_agent.sync_rewards(platform_config.reward_per_share)?;
+    ai_agent.status = to;
+
+    emit!(AgentStatusChanged {
+        agent_id,
+        owner: ctx.accounts.owner.key(),
+        previous_status: from,
+        status: to,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Propose a new owner for an agent; they take over once they accept.
+// Proposing the default key withdraws a pending transfer.
+pub fn propose_agent_owner(ctx: Context<ManageAiAgent>, agent_id: u64, new_owner: Pubkey) -> Result<()> {
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let clock = Clock::get()?;
+
+    // Tokenized agents change hands by transferring the NFT
+    require!(!ai_agent.is_tokenized(), SoreinError::AgentTokenized);
+    require!(new_owner != ai_agent.owner, SoreinError::InvalidNewOwner);
+    ai_agent.pending_owner = new_owner;
+
+    emit!(AgentOwnerProposed {
+        agent_id,
+        owner: ai_agent.owner,
+        pending_owner: new_owner,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Accept a proposed agent ownership transfer
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct AcceptAgentOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump,
+        constraint = ai_agent.pending_owner == new_owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    #[account(
+        mut,
+        seeds = [b"owner-agents", ai_agent.owner.as_ref()],
+        bump = previous_owner_agents.bump
+    )]
+    pub previous_owner_agents: Account<'info, OwnerAgents>,
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = OwnerAgents::SPACE,
+        seeds = [b"owner-agents", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_agents: Account<'info, OwnerAgents>,
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn accept_agent_owner(ctx: Context<AcceptAgentOwner>, agent_id: u64) -> Result<()> {
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let new_owner_agents = &mut ctx.accounts.new_owner_agents;
+    let clock = Clock::get()?;
+
+    // Move the agent between the owners' limits
+    if new_owner_agents.owner == Pubkey::default() {
+        new_owner_agents.init(ctx.accounts.new_owner.key(), ctx.bumps.new_owner_agents);
+    }
+    new_owner_agents.add_agent()?;
+    ctx.accounts.previous_owner_agents.remove_agent()?;
+
+    let previous_owner = ai_agent.owner;
+    ai_agent.owner = ctx.accounts.new_owner.key();
+    ai_agent.pending_owner = Pubkey::default();
+
+    emit!(AgentOwnerTransferred {
+        agent_id,
+        previous_owner,
+        new_owner: ai_agent.owner,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Record the current holder of a tokenized agent's NFT as its owner, moving the agent between the
+// owners' counters. Anyone may call this, e.g. a previous holder freeing up their agent limit.
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct SyncAgentOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump,
+        constraint = ai_agent.is_tokenized() @ SoreinError::AgentNotTokenized,
+        constraint = ai_agent.is_owner(&holder_nft_account.owner, Some(&holder_nft_account)) @ ErrorCode::Unauthorized
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    // The holder's token account for the agent NFT
+    pub holder_nft_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"owner-agents", ai_agent.owner.as_ref()],
+        bump = previous_owner_agents.bump
+    )]
+    pub previous_owner_agents: Account<'info, OwnerAgents>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = OwnerAgents::SPACE,
+        seeds = [b"owner-agents", holder_nft_account.owner.as_ref()],
+        bump
+    )]
+    pub holder_agents: Account<'info, OwnerAgents>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sync_agent_owner(ctx: Context<SyncAgentOwner>, agent_id: u64) -> Result<()> {
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    let holder = ctx.accounts.holder_nft_account.owner;
+    let clock = Clock::get()?;
+
+    require!(holder != ai_agent.owner, SoreinError::InvalidNewOwner);
+
+    // The holder needs room under their agent limit; until then the agent stays counted against the
+    // previous owner, although ownership already follows the NFT
+    let holder_agents = &mut ctx.accounts.holder_agents;
+    if holder_agents.owner == Pubkey::default() {
+        holder_agents.init(holder, ctx.bumps.holder_agents);
+    }
+    holder_agents.add_agent()?;
+    ctx.accounts.previous_owner_agents.remove_agent()?;
+
+    let previous_owner = ai_agent.owner;
+    ai_agent.owner = holder;
+
+    emit!(AgentOwnerTransferred {
+        agent_id,
+        previous_owner,
+        new_owner: holder,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Mint a 1/1 Metaplex NFT for an AI agent; from then on its holder owns the agent
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct TokenizeAiAgent<'info> {
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"agent-mint".as_ref(), &agent_id.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = platform_config,
+        mint::freeze_authority = platform_config,
+        mint::token_program = token_program
+    )]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: metadata PDA of nft_mint, created and verified by the token metadata program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: master edition PDA of nft_mint, created and verified by the token metadata program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    // Unlike the staking instructions this takes SPL Token only, not the token interface:
+    // Token Metadata's CreateMasterEditionV3 rejects Token-2022 mints
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: address is checked against the token metadata program ID
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn tokenize_ai_agent(ctx: Context<TokenizeAiAgent>, agent_id: u64, uri: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let ai_agent = &ctx.accounts.ai_agent;
+
+    require!(uri.len() <= MAX_URI_LENGTH, SoreinError::MetadataTooLarge);
+    require!(!ai_agent.is_tokenized(), SoreinError::AgentTokenized);
+    require!(ai_agent.status != AgentStatus::Retired, SoreinError::InvalidAgentStatus);
+
+    let nft_accounts = AgentNftAccounts {
+        platform_config: ctx.accounts.platform_config.to_account_info(),
+        nft_mint: ctx.accounts.nft_mint.to_account_info(),
+        owner_nft_account: ctx.accounts.owner_nft_account.to_account_info(),
+        metadata: ctx.accounts.metadata.to_account_info(),
+        master_edition: ctx.accounts.master_edition.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    nft_accounts.mint(ctx.accounts.platform_config.bump, ai_agent.name.clone(), uri.clone())?;
+
+    let ai_agent = &mut ctx.accounts.ai_agent;
+    ai_agent.nft_mint = ctx.accounts.nft_mint.key();
+    ai_agent.uri = uri;
+    ai_agent.pending_owner = Pubkey::default();
+
+    emit!(AgentTokenized {
+        agent_id,
+        owner: ai_agent.owner,
+        nft_mint: ai_agent.nft_mint,
+        uri: ai_agent.uri.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Accounts that mint an agent's NFT, shared by register_ai_agent and tokenize_ai_agent
+struct AgentNftAccounts<'info> {
+    platform_config: AccountInfo<'info>,
+    nft_mint: AccountInfo<'info>,
+    owner_nft_account: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    token_metadata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+}
+
+impl<'info> AgentNftAccounts<'info> {
+    // Mint the agent's single token to its owner and attach its metadata and master edition,
+    // all signed by the platform config PDA
+    fn mint(&self, platform_config_bump: u8, name: String, uri: String) -> Result<()> {
+        let bump = [platform_config_bump];
+        let seeds: &[&[u8]] = &[b"platform-config", &bump];
+        let signer = &[seeds];
+
+        let cpi_accounts = MintTo {
+            mint: self.nft_mint.clone(),
+            to: self.owner_nft_account.clone(),
+            authority: self.platform_config.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, signer);
+        token_interface::mint_to(cpi_ctx, 1)?;
+
+        // Attach metadata, with the platform config as update authority
+        CreateMetadataAccountV3Cpi::new(
+            &self.token_metadata_program,
+            CreateMetadataAccountV3CpiAccounts {
+                metadata: &self.metadata,
+                mint: &self.nft_mint,
+                mint_authority: &self.platform_config,
+                payer: &self.owner,
+                update_authority: (&self.platform_config, true),
+                system_program: &self.system_program,
+                rent: None,
+            },
+            CreateMetadataAccountV3InstructionArgs {
+                data: DataV2 {
+                    name,
+                    symbol: AGENT_NFT_SYMBOL.to_string(),
+                    uri,
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                },
+                is_mutable: true,
+                collection_details: None,
+            },
+        )
+        .invoke_signed(signer)?;
+
+        // The master edition takes over the mint authority, capping the supply at one
+        CreateMasterEditionV3Cpi::new(
+            &self.token_metadata_program,
+            CreateMasterEditionV3CpiAccounts {
+                edition: &self.master_edition,
+                mint: &self.nft_mint,
+                update_authority: &self.platform_config,
+                mint_authority: &self.platform_config,
+                payer: &self.owner,
+                metadata: &self.metadata,
+                token_program: &self.token_program,
+                system_program: &self.system_program,
+                rent: None,
+            },
+            CreateMasterEditionV3InstructionArgs { max_supply: Some(0) },
+        )
+        .invoke_signed(signer)?;
+        Ok(())
+    }
+}
+
+// Close a retired AI agent. Stake positions still open on it move to a ClosedAgent account,
+// so dust left by stakers can't keep the agent open; they exit through exit_closed_agent_position.
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct CloseAiAgent<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = ai_agent.bump,
+        constraint = ai_agent.is_owner(&owner.key(), owner_nft_account.as_deref()) @ ErrorCode::Unauthorized
+    )]
+    pub ai_agent: Account<'info, AiAgent>,
+    // Counter of the recorded owner, which a tokenized agent counts against until synced to its holder
+    #[account(
+        mut,
+        seeds = [b"owner-agents", ai_agent.owner.as_ref()],
+        bump = owner_agents.bump
+    )]
+    pub owner_agents: Account<'info, OwnerAgents>,
+    // Required when positions are still open on the agent
+    #[account(
+        init,
+        payer = owner,
+        space = ClosedAgent::SPACE,
+        seeds = [b"closed-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump
+    )]
+    pub closed_agent: Option<Account<'info, ClosedAgent>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub owner_nft_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn close_ai_agent(ctx: Context<CloseAiAgent>, agent_id: u64) -> Result<()> {
+    let ai_agent = &ctx.accounts.ai_agent;
+    let clock = Clock::get()?;
+
+    require!(ai_agent.status == AgentStatus::Retired, SoreinError::InvalidAgentStatus);
+    // Keep what open positions need to settle their rewards and exit
+    let has_positions = ai_agent.staked_amount > 0 || ai_agent.position_count > 0;
+    match ctx.accounts.closed_agent.as_deref_mut() {
+        Some(closed_agent) if has_positions => {
+            closed_agent.init(ai_agent, ctx.accounts.owner.key(), ctx.bumps.closed_agent);
+        }
+        None if !has_positions => {}
+        _ => return err!(SoreinError::AgentHasOpenPositions),
+    }
+    ctx.accounts.owner_agents.remove_agent()?;
+
+    emit!(AgentClosed {
+        agent_id,
+        owner: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// Leave a position on a closed agent: pays out its rewards, queues its stake for immediate
+// withdrawal and closes it. The last position to leave closes the ClosedAgent account.
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct ExitClosedAgentPosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        constraint = !platform_config.paused @ SoreinError::PlatformPaused
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"closed-agent".as_ref(), &agent_id.to_le_bytes()],
+        bump = closed_agent.bump
+    )]
+    pub closed_agent: Account<'info, ClosedAgent>,
+    /// CHECK: receives the ClosedAgent rent; address checked against the recorded owner
+    #[account(mut, address = closed_agent.owner @ ErrorCode::Unauthorized)]
+    pub closed_agent_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user-stake", user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserStake>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"stake-position", user.key().as_ref(), &agent_id.to_le_bytes()],
+        bump = stake_position.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(
+        mut,
+        seeds = [b"stake-checkpoints", user.key().as_ref()],
+        bump = stake_checkpoints.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_checkpoints: Account<'info, StakeCheckpoints>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        address = platform_config.reward_mint,
+        mint::token_program = token_program
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = platform_config.reward_vault @ SoreinError::InvalidVault,
+        token::mint = reward_mint,
+        token::authority = platform_config,
+        token::token_program = token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn exit_closed_agent_position(ctx: Context<ExitClosedAgentPosition>, agent_id: u64) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let closed_agent = &mut ctx.accounts.closed_agent;
+    let user_stake = &mut ctx.accounts.user_stake;
+    let stake_position = &mut ctx.accounts.stake_position;
+    let clock = Clock::get()?;
+
+    require!(!user_stake.is_locked(clock.unix_timestamp), SoreinError::StakeLocked);
+
+    // The agent was retired before it closed, so its accumulator is final
+    stake_position.settle_rewards(closed_agent.reward_per_share)?;
+    let reward_to_claim = stake_position.accumulated_rewards;
+    stake_position.accumulated_rewards = 0;
+
+    // Queue the remaining stake with no cooldown, as for any retired agent
+    let amount = stake_position.amount;
+    if amount > 0 {
+        user_stake.queue_withdrawal(agent_id, amount, clock.unix_timestamp)?;
+        stake_position.amount = 0;
+        user_stake.staked_amount = user_stake.staked_amount.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+        closed_agent.staked_amount = closed_agent.staked_amount.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+        platform_config.total_staked = platform_config.total_staked.checked_sub(amount).ok_or(SoreinError::InvalidUnstakeAmount)?;
+        user_stake.last_stake_update = clock.unix_timestamp;
+        ctx.accounts.stake_checkpoints.record(clock.slot, user_stake.staked_amount, user_stake.lockup_end);
+    }
+
+    closed_agent.position_count = closed_agent.position_count.checked_sub(1).ok_or(SoreinError::ArithmeticError)?;
+    user_stake.position_count = user_stake.position_count.checked_sub(1).ok_or(SoreinError::ArithmeticError)?;
+    if closed_agent.position_count == 0 {
+        closed_agent.close(ctx.accounts.closed_agent_owner.to_account_info())?;
+    }
+
+    // Transfer rewards from the reward vault to user, signed by the platform config PDA
+    if reward_to_claim > 0 {
+        let bump = [ctx.accounts.platform_config.bump];
+        let seeds: &[&[u8]] = &[b"platform-config", &bump];
+        let signer = &[seeds];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.platform_config.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, reward_to_claim, ctx.accounts.reward_mint.decimals)?;
+    }
+
+    msg!(
+        "User {} exited closed agent {}: {} queued for withdrawal, {} rewards claimed",
+        ctx.accounts.user.key(),
+        agent_id,
+        amount,
+        reward_to_claim
+    );
     Ok(())
 }
 
@@ -1,326 +1,434 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::clock::Clock;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+pub mod council;
+pub mod error;
+pub mod events;
+pub mod governance;
+pub mod governance_rewards;
+pub mod instructions;
+// Reward accounting math shared by the staking instructions
+pub mod math;
+pub mod performance;
+pub mod state;
+pub mod treasury;
+
+// The program module below refers to the accounts structs (and their generated client
+// modules) from the crate root, so each instruction module is re-exported here. The
+// handler names also exist as program entrypoints; callers go through the program module.
+#[allow(ambiguous_glob_reexports)]
+pub use council::*;
+#[allow(ambiguous_glob_reexports)]
+pub use governance::*;
+#[allow(ambiguous_glob_reexports)]
+pub use governance_rewards::*;
+#[allow(ambiguous_glob_reexports)]
+pub use instructions::*;
+#[allow(ambiguous_glob_reexports)]
+pub use performance::*;
+#[allow(ambiguous_glob_reexports)]
+pub use treasury::*;
+
+use state::{
+    GovernanceThresholds, LockupMode, LockupTier, ProposalAccountMeta, ProposalAction, ProposalContent, ProposalType,
+    VoteType,
+};
 
 // Declare the program ID for the smart contract
-declare_id!("YourProgramIDHere"); // Replace with your actual program ID after deployment
-
-// Constants for staking and rewards
-const STAKING_COOLDOWN: i64 = 86400; // 24 hours in seconds for unstaking cooldown
-const REWARD_RATE: u64 = 100; // Reward rate per epoch (adjustable)
-const EPOCH_DURATION: i64 = 604800; // 7 days in seconds for reward epoch
-
-// Custom error codes for the program
-#[error_code]
-pub enum FabeonError {
-    #[msg("Insufficient stake amount")]
-    InsufficientStake,
-    #[msg("Staking cooldown not completed")]
-    CooldownNotCompleted,
-    #[msg("Invalid AI agent owner")]
-    InvalidOwner,
-    #[msg("Governance proposal already active")]
-    ProposalActive,
-    #[msg("Insufficient voting power")]
-    InsufficientVotingPower,
-    #[msg("Reward pool depleted")]
-    RewardPoolDepleted,
-}
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
-// Account structure for an AI Agent
-#[account]
-pub struct AIAgent {
-    pub owner: Pubkey, // Owner of the AI agent
-    pub staked_amount: u64, // Amount of tokens staked
-    pub last_stake_time: i64, // Timestamp of last staking action
-    pub accumulated_rewards: u64, // Accumulated rewards for this agent
-    pub is_active: bool, // Whether the agent is active
-    pub bump: u8, // Bump seed for PDA derivation
-}
+// Program entrypoints; each one hands off to the handler in its module
+#[program]
+pub mod sorein_ai {
+    use super::*;
 
-// Account structure for Governance Proposal
-#[account]
-pub struct GovernanceProposal {
-    pub proposer: Pubkey, // Who proposed this
-    pub description: String, // Description of the proposal (limited length)
-    pub yes_votes: u64, // Votes in favor
-    pub no_votes: u64, // Votes against
-    pub start_time: i64, // When voting started
-    pub end_time: i64, // When voting ends
-    pub is_active: bool, // Whether the proposal is active
-    pub bump: u8, // Bump seed for PDA derivation
-}
+    // Platform configuration
 
-// Account structure for Reward Pool
-#[account]
-pub struct RewardPool {
-    pub total_rewards: u64, // Total rewards available in the pool
-    pub last_updated: i64, // Last time the pool was updated
-    pub bump: u8, // Bump seed for PDA derivation
-}
+    pub fn initialize_platform(
+        ctx: Context<InitializePlatform>,
+        reward_rate_bps: u64,
+        min_stake_amount: u64,
+        epoch_duration: i64,
+        unstake_cooldown: i64,
+    ) -> Result<()> {
+        instructions::initialize_platform(ctx, reward_rate_bps, min_stake_amount, epoch_duration, unstake_cooldown)
+    }
 
-// Program entrypoint and instructions
-#[program]
-pub mod Omelix_ai {
-    use super::*;
+    pub fn update_platform_config(
+        ctx: Context<UpdatePlatformConfig>,
+        reward_rate_bps: u64,
+        min_stake_amount: u64,
+        epoch_duration: i64,
+        unstake_cooldown: i64,
+    ) -> Result<()> {
+        instructions::update_platform_config(ctx, reward_rate_bps, min_stake_amount, epoch_duration, unstake_cooldown)
+    }
 
-    // Initialize the reward pool
-    pub fn initialize_reward_pool(ctx: Context<InitializeRewardPool>, initial_rewards: u64) -> Result<()> {
-        let reward_pool = &mut ctx.accounts.reward_pool;
-        reward_pool.total_rewards = initial_rewards;
-        reward_pool.last_updated = Clock::get()?.unix_timestamp;
-        reward_pool.bump = ctx.bumps.reward_pool;
-        Ok(())
-    }
-
-    // Register a new AI agent with staking
-    pub fn register_ai_agent(ctx: Context<RegisterAIAgent>, stake_amount: u64) -> Result<()> {
-        let ai_agent = &mut ctx.accounts.ai_agent;
-        let token_program = &ctx.accounts.token_program;
-        let user_token_account = &ctx.accounts.user_token_account;
-        let staking_vault = &ctx.accounts.staking_vault;
-
-        // Ensure stake amount is greater than zero
-        require!(stake_amount > 0, RexoulError::InsufficientStake);
-
-        // Transfer tokens from user to staking vault
-        let cpi_accounts = Transfer {
-            from: user_token_account.to_account_info(),
-            to: staking_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        let cpi_program = token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, stake_amount)?;
-
-        // Initialize AI agent data
-        ai_agent.owner = ctx.accounts.user.key();
-        ai_agent.staked_amount = stake_amount;
-        ai_agent.last_stake_time = Clock::get()?.unix_timestamp;
-        ai_agent.accumulated_rewards = 0;
-        ai_agent.is_active = true;
-        ai_agent.bump = ctx.bumps.ai_agent;
-
-        Ok(())
-    }
-
-    // Unstake tokens from an AI agent
-    pub fn unstake_ai_agent(ctx: Context<UnstakeAIAgent>) -> Result<()> {
-        let ai_agent = &mut ctx.accounts.ai_agent;
-        let token_program = &ctx.accounts.token_program;
-        let user_token_account = &ctx.accounts.user_token_account;
-        let staking_vault = &ctx.accounts.staking_vault;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        // Check if cooldown period has passed
-        require!(
-            current_time >= ai_agent.last_stake_time + STAKING_COOLDOWN,
-            OntoraError::CooldownNotCompleted
-        );
-
-        // Check if the caller is the owner
-        require!(ai_agent.owner == ctx.accounts.user.key(), OntoraError::InvalidOwner);
-
-        // Transfer staked tokens back to user
-        let seeds = &[b"ai_agent", ai_agent.owner.as_ref(), &[ai_agent.bump]];
-        let signer = &[&seeds[..]];
-        let cpi_accounts = Transfer {
-            from: staking_vault.to_account_info(),
-            to: user_token_account.to_account_info(),
-            authority: ai_agent.to_account_info(),
-        };
-        let cpi_program = token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, ai_agent.staked_amount)?;
-
-        // Update AI agent state
-        ai_agent.staked_amount = 0;
-        ai_agent.is_active = false;
-        ai_agent.last_stake_time = current_time;
-
-        Ok(())
-    }
-
-    // Distribute rewards to AI agents
-    pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
-        let reward_pool = &mut ctx.accounts.reward_pool;
-        let ai_agent = &mut ctx.accounts.ai_agent;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        // Check if enough time has passed since last update (epoch duration)
-        require!(
-            current_time >= reward_pool.last_updated + EPOCH_DURATION,
-            OntoraError::CooldownNotCompleted
-        );
-
-        // Calculate rewards based on staked amount and reward rate
-        let reward = ai_agent.staked_amount * REWARD_RATE / 1000; // Example: 0.1% of staked amount per epoch
-        require!(reward_pool.total_rewards >= reward, OntoraError::RewardPoolDepleted);
-
-        // Update reward pool and agent rewards
-        reward_pool.total_rewards -= reward;
-        ai_agent.accumulated_rewards += reward;
-        reward_pool.last_updated = current_time;
-
-        Ok(())
-    }
-
-    // Claim accumulated rewards
-    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
-        let ai_agent = &mut ctx.accounts.ai_agent;
-        let token_program = &ctx.accounts.token_program;
-        let user_token_account = &ctx.accounts.user_token_account;
-        let reward_vault = &ctx.accounts.reward_vault;
-
-        // Check if the caller is the owner
-        require!(ai_agent.owner == ctx.accounts.user.key(), OntoraError::InvalidOwner);
-
-        // Check if there are rewards to claim
-        require!(ai_agent.accumulated_rewards > 0, OntoraError::RewardPoolDepleted);
-
-        // Transfer rewards from vault to user
-        let seeds = &[b"reward_vault", &[ctx.accounts.reward_pool.bump]];
-        let signer = &[&seeds[..]];
-        let cpi_accounts = Transfer {
-            from: reward_vault.to_account_info(),
-            to: user_token_account.to_account_info(),
-            authority: ctx.accounts.reward_pool.to_account_info(),
-        };
-        let cpi_program = token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, ai_agent.accumulated_rewards)?;
-
-        // Reset accumulated rewards
-        ai_agent.accumulated_rewards = 0;
-
-        Ok(())
-    }
-
-    // Create a governance proposal
-    pub fn create_proposal(ctx: Context<CreateProposal>, description: String, duration: i64) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        // Ensure description is not empty and within length limits (e.g., 200 characters)
-        require!(description.len() > 0 && description.len() <= 200, OntoraError::InvalidOwner);
-
-        // Initialize proposal data
-        proposal.proposer = ctx.accounts.user.key();
-        proposal.description = description;
-        proposal.yes_votes = 0;
-        proposal.no_votes = 0;
-        proposal.start_time = current_time;
-        proposal.end_time = current_time + duration;
-        proposal.is_active = true;
-        proposal.bump = ctx.bumps.proposal;
-
-        Ok(())
-    }
-
-    // Vote on a governance proposal
-    pub fn vote_proposal(ctx: Context<VoteProposal>, in_favor: bool) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
-        let ai_agent = &ctx.accounts.ai_agent;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        // Ensure proposal is active and voting period is ongoing
-        require!(proposal.is_active, OntoraError::ProposalActive);
-        require!(current_time < proposal.end_time, OntoraError::ProposalActive);
-
-        // Ensure voter is the owner of the AI agent
-        require!(ai_agent.owner == ctx.accounts.user.key(), OntoraError::InvalidOwner);
+    pub fn configure_lockup(
+        ctx: Context<UpdatePlatformConfig>,
+        lockup_mode: LockupMode,
+        min_lockup_period: i64,
+        max_lockup_period: i64,
+        max_lockup_multiplier_bps: u64,
+        lockup_tiers: Vec<LockupTier>,
+    ) -> Result<()> {
+        instructions::configure_lockup(
+            ctx,
+            lockup_mode,
+            min_lockup_period,
+            max_lockup_period,
+            max_lockup_multiplier_bps,
+            lockup_tiers,
+        )
+    }
 
-        // Calculate voting power based on staked amount
-        let voting_power = ai_agent.staked_amount / 100; // Example: 1 vote per 100 tokens staked
-        require!(voting_power > 0, OntoraError::InsufficientVotingPower);
+    pub fn set_default_thresholds(ctx: Context<UpdatePlatformConfig>, thresholds: GovernanceThresholds) -> Result<()> {
+        instructions::set_default_thresholds(ctx, thresholds)
+    }
 
-        // Record the vote
-        if in_favor {
-            proposal.yes_votes += voting_power;
-        } else {
-            proposal.no_votes += voting_power;
-        }
+    pub fn set_threshold_override(
+        ctx: Context<UpdatePlatformConfig>,
+        proposal_type: ProposalType,
+        thresholds: Option<GovernanceThresholds>,
+    ) -> Result<()> {
+        instructions::set_threshold_override(ctx, proposal_type, thresholds)
+    }
 
-        Ok(())
+    pub fn configure_timelock(
+        ctx: Context<UpdatePlatformConfig>,
+        execution_delay: i64,
+        critical_execution_delay: i64,
+        execution_grace_period: i64,
+    ) -> Result<()> {
+        instructions::configure_timelock(ctx, execution_delay, critical_execution_delay, execution_grace_period)
     }
-}
 
-// Context structs for instruction validation
-#[derive(Accounts)]
-pub struct InitializeRewardPool<'info> {
-    #[account(init, payer = user, space = 8 + 16 + 8, seeds = [b"reward_pool"], bump)]
-    pub reward_pool: Account<'info, RewardPool>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn configure_proposal_requirements(
+        ctx: Context<UpdatePlatformConfig>,
+        proposal_deposit: u64,
+        min_stake_to_propose: u64,
+        min_voting_duration: u64,
+        max_voting_duration: u64,
+    ) -> Result<()> {
+        instructions::configure_proposal_requirements(
+            ctx,
+            proposal_deposit,
+            min_stake_to_propose,
+            min_voting_duration,
+            max_voting_duration,
+        )
+    }
 
-#[derive(Accounts)]
-pub struct RegisterAIAgent<'info> {
-    #[account(init, payer = user, space = 8 + 32 + 8 + 8 + 8 + 1 + 1, seeds = [b"ai_agent", user.key().as_ref()], bump)]
-    pub ai_agent: Account<'info, AIAgent>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub staking_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn configure_governance_rewards(
+        ctx: Context<UpdatePlatformConfig>,
+        voting_reward_budget: u64,
+        proposer_reward: u64,
+        proposer_reward_epoch_cap: u64,
+    ) -> Result<()> {
+        instructions::configure_governance_rewards(ctx, voting_reward_budget, proposer_reward, proposer_reward_epoch_cap)
+    }
 
-#[derive(Accounts)]
-pub struct UnstakeAIAgent<'info> {
-    #[account(mut, has_one = owner @ OntoraError::InvalidOwner)]
-    pub ai_agent: Account<'info, AIAgent>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub staking_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-}
+    pub fn set_guardian(ctx: Context<UpdatePlatformConfig>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian(ctx, guardian)
+    }
 
-#[derive(Accounts)]
-pub struct DistributeRewards<'info> {
-    #[account(mut)]
-    pub reward_pool: Account<'info, RewardPool>,
-    #[account(mut)]
-    pub ai_agent: Account<'info, AIAgent>,
-    pub user: Signer<'info>,
-}
+    pub fn set_governance_enabled(ctx: Context<UpdatePlatformConfig>, enabled: bool) -> Result<()> {
+        instructions::set_governance_enabled(ctx, enabled)
+    }
 
-#[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account(mut)]
-    pub ai_agent: Account<'info, AIAgent>,
-    #[account(mut)]
-    pub reward_pool: Account<'info, RewardPool>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub reward_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-}
+    // AI agents and staking
 
-#[derive(Accounts)]
-pub struct CreateProposal<'info> {
-    #[account(init, payer = user, space = 8 + 32 + 200 + 8 + 8 + 8 + 8 + 1 + 1, seeds = [b"proposal", user.key().as_ref()], bump)]
-    pub proposal: Account<'info, GovernanceProposal>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn register_ai_agent(
+        ctx: Context<RegisterAiAgent>,
+        name: String,
+        description: String,
+        uri: Option<String>,
+    ) -> Result<()> {
+        instructions::register_ai_agent(ctx, name, description, uri)
+    }
+
+    pub fn stake_on_agent(ctx: Context<StakeOnAgent>, agent_id: u64, amount: u64) -> Result<()> {
+        instructions::stake_on_agent(ctx, agent_id, amount)
+    }
+
+    pub fn unstake_from_agent(ctx: Context<UnstakeFromAgent>, agent_id: u64, amount: u64) -> Result<()> {
+        instructions::unstake_from_agent(ctx, agent_id, amount)
+    }
+
+    pub fn lock_stake(ctx: Context<LockStake>, lockup_period: i64) -> Result<()> {
+        instructions::lock_stake(ctx, lockup_period)
+    }
+
+    pub fn withdraw_unlocked(ctx: Context<WithdrawUnlocked>) -> Result<()> {
+        instructions::withdraw_unlocked(ctx)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, agent_id: u64) -> Result<()> {
+        instructions::claim_rewards(ctx, agent_id)
+    }
+
+    pub fn close_stake_position(ctx: Context<CloseStakePosition>, agent_id: u64) -> Result<()> {
+        instructions::close_stake_position(ctx, agent_id)
+    }
+
+    pub fn update_agent_metadata(
+        ctx: Context<ManageAiAgent>,
+        agent_id: u64,
+        name: String,
+        description: String,
+    ) -> Result<()> {
+        instructions::update_agent_metadata(ctx, agent_id, name, description)
+    }
+
+    pub fn pause_agent(ctx: Context<ManageAiAgent>, agent_id: u64) -> Result<()> {
+        instructions::pause_agent(ctx, agent_id)
+    }
+
+    pub fn resume_agent(ctx: Context<ManageAiAgent>, agent_id: u64) -> Result<()> {
+        instructions::resume_agent(ctx, agent_id)
+    }
+
+    pub fn retire_agent(ctx: Context<ManageAiAgent>, agent_id: u64) -> Result<()> {
+        instructions::retire_agent(ctx, agent_id)
+    }
+
+    pub fn propose_agent_owner(ctx: Context<ManageAiAgent>, agent_id: u64, new_owner: Pubkey) -> Result<()> {
+        instructions::propose_agent_owner(ctx, agent_id, new_owner)
+    }
+
+    pub fn accept_agent_owner(ctx: Context<AcceptAgentOwner>, agent_id: u64) -> Result<()> {
+        instructions::accept_agent_owner(ctx, agent_id)
+    }
+
+    pub fn sync_agent_owner(ctx: Context<SyncAgentOwner>, agent_id: u64) -> Result<()> {
+        instructions::sync_agent_owner(ctx, agent_id)
+    }
+
+    pub fn tokenize_ai_agent(ctx: Context<TokenizeAiAgent>, agent_id: u64, uri: String) -> Result<()> {
+        instructions::tokenize_ai_agent(ctx, agent_id, uri)
+    }
+
+    pub fn close_ai_agent(ctx: Context<CloseAiAgent>, agent_id: u64) -> Result<()> {
+        instructions::close_ai_agent(ctx, agent_id)
+    }
+
+    pub fn exit_closed_agent_position(ctx: Context<ExitClosedAgentPosition>, agent_id: u64) -> Result<()> {
+        instructions::exit_closed_agent_position(ctx, agent_id)
+    }
+
+    // Performance scores
+
+    pub fn set_score_feed(ctx: Context<SetScoreFeed>, score_feed: Pubkey, score_feed_program: Pubkey) -> Result<()> {
+        performance::set_score_feed(ctx, score_feed, score_feed_program)
+    }
+
+    pub fn register_evaluator(ctx: Context<RegisterEvaluator>, key: Pubkey) -> Result<()> {
+        performance::register_evaluator(ctx, key)
+    }
+
+    pub fn remove_evaluator(ctx: Context<RemoveEvaluator>) -> Result<()> {
+        performance::remove_evaluator(ctx)
+    }
+
+    pub fn execute_register_evaluator(
+        ctx: Context<ExecuteRegisterEvaluator>,
+        proposal_id: u64,
+        key: Pubkey,
+    ) -> Result<()> {
+        performance::execute_register_evaluator(ctx, proposal_id, key)
+    }
+
+    pub fn execute_remove_evaluator(ctx: Context<ExecuteRemoveEvaluator>, proposal_id: u64) -> Result<()> {
+        performance::execute_remove_evaluator(ctx, proposal_id)
+    }
+
+    pub fn update_performance_score(ctx: Context<UpdatePerformanceScore>, agent_id: u64) -> Result<()> {
+        performance::update_performance_score(ctx, agent_id)
+    }
+
+    // Governance proposals
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        content: ProposalContent,
+        proposal_type: ProposalType,
+        vote_type: VoteType,
+        voting_duration: u64,
+        action: Option<ProposalAction>,
+    ) -> Result<()> {
+        governance::create_proposal(ctx, content, proposal_type, vote_type, voting_duration, action)
+    }
+
+    pub fn update_draft_proposal(
+        ctx: Context<UpdateDraftProposal>,
+        proposal_id: u64,
+        title: String,
+        description: String,
+        options: Vec<String>,
+    ) -> Result<()> {
+        governance::update_draft_proposal(ctx, proposal_id, title, description, options)
+    }
+
+    pub fn add_signatory(ctx: Context<AddSignatory>, proposal_id: u64, signatory: Pubkey) -> Result<()> {
+        governance::add_signatory(ctx, proposal_id, signatory)
+    }
+
+    pub fn remove_signatory(ctx: Context<RemoveSignatory>, proposal_id: u64) -> Result<()> {
+        governance::remove_signatory(ctx, proposal_id)
+    }
+
+    pub fn sign_off_signatory(ctx: Context<SignOffSignatory>, proposal_id: u64) -> Result<()> {
+        governance::sign_off_signatory(ctx, proposal_id)
+    }
+
+    pub fn sign_off_proposal(ctx: Context<SignOffProposal>, proposal_id: u64) -> Result<()> {
+        governance::sign_off_proposal(ctx, proposal_id)
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>, proposal_id: u64) -> Result<()> {
+        governance::cancel_proposal(ctx, proposal_id)
+    }
+
+    pub fn cast_vote(ctx: Context<CastVote>, proposal_id: u64, choices: Vec<u8>) -> Result<()> {
+        governance::cast_vote(ctx, proposal_id, choices)
+    }
+
+    pub fn change_vote(ctx: Context<ChangeVote>, proposal_id: u64, choices: Vec<u8>) -> Result<()> {
+        governance::change_vote(ctx, proposal_id, choices)
+    }
+
+    pub fn relinquish_vote(ctx: Context<RelinquishVote>, proposal_id: u64) -> Result<()> {
+        governance::relinquish_vote(ctx, proposal_id)
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>, proposal_id: u64) -> Result<()> {
+        governance::finalize_proposal(ctx, proposal_id)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+        governance::execute_proposal(ctx, proposal_id)
+    }
+
+    pub fn insert_proposal_instruction(
+        ctx: Context<InsertProposalInstruction>,
+        proposal_id: u64,
+        index: u16,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        governance::insert_proposal_instruction(ctx, proposal_id, index, program_id, accounts, data)
+    }
+
+    pub fn append_instruction_data(
+        ctx: Context<AppendInstructionData>,
+        proposal_id: u64,
+        index: u16,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        governance::append_instruction_data(ctx, proposal_id, index, chunk)
+    }
+
+    pub fn execute_proposal_instruction(
+        ctx: Context<ExecuteProposalInstruction>,
+        proposal_id: u64,
+        index: u16,
+    ) -> Result<()> {
+        governance::execute_proposal_instruction(ctx, proposal_id, index)
+    }
+
+    pub fn guardian_cancel_proposal(ctx: Context<GuardianCancelProposal>, proposal_id: u64) -> Result<()> {
+        governance::guardian_cancel_proposal(ctx, proposal_id)
+    }
+
+    pub fn flag_proposal_spam(ctx: Context<FlagProposalSpam>, proposal_id: u64) -> Result<()> {
+        governance::flag_proposal_spam(ctx, proposal_id)
+    }
+
+    pub fn settle_proposal_deposit(ctx: Context<SettleProposalDeposit>, proposal_id: u64) -> Result<()> {
+        governance::settle_proposal_deposit(ctx, proposal_id)
+    }
+
+    pub fn close_proposal(ctx: Context<CloseProposal>, proposal_id: u64) -> Result<()> {
+        governance::close_proposal(ctx, proposal_id)
+    }
+
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+        governance::close_vote_record(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct VoteProposal<'info> {
-    #[account(mut)]
-    pub proposal: Account<'info, GovernanceProposal>,
-    #[account(has_one = owner @ OntoraError::InvalidOwner)]
-    pub ai_agent: Account<'info, AIAgent>,
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub fn close_proposal_instruction(ctx: Context<CloseProposalInstruction>) -> Result<()> {
+        governance::close_proposal_instruction(ctx)
+    }
+
+    pub fn close_signatory_record(ctx: Context<CloseSignatoryRecord>) -> Result<()> {
+        governance::close_signatory_record(ctx)
+    }
+
+    // Vote delegation
+
+    pub fn delegate_votes(ctx: Context<DelegateVotes>) -> Result<()> {
+        governance::delegate_votes(ctx)
+    }
+
+    pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+        governance::undelegate(ctx)
+    }
+
+    pub fn remove_delegator(ctx: Context<RemoveDelegator>) -> Result<()> {
+        governance::remove_delegator(ctx)
+    }
+
+    // Governance rewards
+
+    pub fn initialize_governance_reward_vault(ctx: Context<InitializeGovernanceRewardVault>) -> Result<()> {
+        governance_rewards::initialize_governance_reward_vault(ctx)
+    }
+
+    pub fn claim_voting_reward(ctx: Context<ClaimVotingReward>) -> Result<()> {
+        governance_rewards::claim_voting_reward(ctx)
+    }
+
+    pub fn claim_proposer_reward(ctx: Context<ClaimProposerReward>, proposal_id: u64) -> Result<()> {
+        governance_rewards::claim_proposer_reward(ctx, proposal_id)
+    }
+
+    // Council
+
+    pub fn initialize_council(ctx: Context<InitializeCouncil>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        council::initialize_council(ctx, members, threshold)
+    }
+
+    pub fn council_veto_proposal(ctx: Context<CouncilProposalAction>, proposal_id: u64) -> Result<()> {
+        council::council_veto_proposal(ctx, proposal_id)
+    }
+
+    pub fn council_fast_track_proposal(ctx: Context<CouncilProposalAction>, proposal_id: u64) -> Result<()> {
+        council::council_fast_track_proposal(ctx, proposal_id)
+    }
+
+    pub fn council_set_paused(ctx: Context<CouncilSetPaused>, paused: bool) -> Result<()> {
+        council::council_set_paused(ctx, paused)
+    }
+
+    pub fn execute_council_rotation(ctx: Context<ExecuteCouncilRotation>, proposal_id: u64) -> Result<()> {
+        council::execute_council_rotation(ctx, proposal_id)
+    }
+
+    // Treasury
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        treasury::initialize_treasury(ctx)
+    }
+
+    pub fn create_treasury_ledger(ctx: Context<CreateTreasuryLedger>) -> Result<()> {
+        treasury::create_treasury_ledger(ctx)
+    }
+
+    pub fn execute_treasury_budget(ctx: Context<ExecuteTreasuryBudget>, proposal_id: u64) -> Result<()> {
+        treasury::execute_treasury_budget(ctx, proposal_id)
+    }
+
+    pub fn execute_treasury_spend(ctx: Context<ExecuteTreasurySpend>, proposal_id: u64) -> Result<()> {
+        treasury::execute_treasury_spend(ctx, proposal_id)
+    }
 }
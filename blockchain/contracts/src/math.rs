@@ -0,0 +1,187 @@
+// Pure reward accounting math used by the staking instructions.
+// Kept free of account types so the invariants can be exercised directly from tests.
+
+// Fixed-point scale applied to reward-per-share accumulators
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+// Denominator for basis point rates (10000 = 100%)
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// Rewards emitted to `total_staked` tokens over `elapsed` seconds at `reward_rate_bps` per epoch
+pub fn emission_for_period(
+    total_staked: u64,
+    reward_rate_bps: u64,
+    elapsed: i64,
+    epoch_duration: i64,
+) -> Option<u64> {
+    if total_staked == 0 || elapsed <= 0 {
+        return Some(0);
+    }
+    if epoch_duration <= 0 {
+        return None;
+    }
+    let emission = (total_staked as u128)
+        .checked_mul(reward_rate_bps as u128)?
+        .checked_mul(elapsed as u128)?
+        .checked_div(BPS_DENOMINATOR.checked_mul(epoch_duration as u128)?)?;
+    u64::try_from(emission).ok()
+}
+
+// Accumulator value after spreading `emission` evenly over `total_staked` tokens.
+// Rounds down so the sum of per-share payouts can never exceed what was emitted.
+pub fn accrue_reward_per_share(reward_per_share: u128, emission: u64, total_staked: u64) -> Option<u128> {
+    if total_staked == 0 || emission == 0 {
+        return Some(reward_per_share);
+    }
+    let increment = (emission as u128)
+        .checked_mul(REWARD_PRECISION)?
+        .checked_div(total_staked as u128)?;
+    reward_per_share.checked_add(increment)
+}
+
+// Checkpoint recorded on a stake so only accumulator growth after this point is paid out
+pub fn reward_debt(amount: u64, reward_per_share: u128) -> Option<u128> {
+    (amount as u128).checked_mul(reward_per_share)
+}
+
+// Rewards owed to `amount` tokens for accumulator growth since `reward_debt` was recorded
+pub fn pending_reward(amount: u64, reward_per_share: u128, reward_debt: u128) -> Option<u64> {
+    let accrued = (amount as u128).checked_mul(reward_per_share)?;
+    let pending = accrued.checked_sub(reward_debt)?.checked_div(REWARD_PRECISION)?;
+    u64::try_from(pending).ok()
+}
+
+// Advance an agent's accumulator by the global growth since its last sync.
+// Returns the new agent accumulator; the caller stores `global_reward_per_share` as the new checkpoint.
+pub fn sync_agent_reward_per_share(
+    agent_reward_per_share: u128,
+    agent_checkpoint: u128,
+    global_reward_per_share: u128,
+) -> Option<u128> {
+    let growth = global_reward_per_share.checked_sub(agent_checkpoint)?;
+    agent_reward_per_share.checked_add(growth)
+}
+
+// Voting power multiplier for stake with no lock remaining (1.0x in basis points)
+pub const BASE_MULTIPLIER_BPS: u64 = 10_000;
+
+// Linear lockup multiplier: 1.0x with nothing left on the lock, rising to `max_multiplier_bps`
+// when `max_lockup_period` or more remains, so the bonus decays as the lock approaches expiry
+pub fn linear_lockup_multiplier_bps(remaining: i64, max_lockup_period: i64, max_multiplier_bps: u64) -> Option<u64> {
+    if remaining <= 0 || max_lockup_period <= 0 || max_multiplier_bps <= BASE_MULTIPLIER_BPS {
+        return Some(BASE_MULTIPLIER_BPS);
+    }
+    let remaining = remaining.min(max_lockup_period) as u128;
+    let bonus = ((max_multiplier_bps - BASE_MULTIPLIER_BPS) as u128)
+        .checked_mul(remaining)?
+        .checked_div(max_lockup_period as u128)?;
+    BASE_MULTIPLIER_BPS.checked_add(u64::try_from(bonus).ok()?)
+}
+
+// Tiered lockup multiplier: the highest multiplier among tiers whose period is still covered by
+// the remaining lock time, so a position steps down through the tiers as the lock runs out.
+// `tiers` are (period, multiplier_bps) pairs; a zero period marks an unused slot.
+pub fn tiered_lockup_multiplier_bps(remaining: i64, tiers: &[(i64, u64)]) -> u64 {
+    tiers
+        .iter()
+        .filter(|(period, _)| *period > 0 && remaining >= *period)
+        .map(|(_, multiplier_bps)| *multiplier_bps)
+        .fold(BASE_MULTIPLIER_BPS, u64::max)
+}
+
+// Voting power of `amount` staked tokens under a multiplier in basis points
+pub fn voting_power(amount: u64, multiplier_bps: u64) -> Option<u64> {
+    let power = (amount as u128)
+        .checked_mul(multiplier_bps as u128)?
+        .checked_div(BPS_DENOMINATOR)?;
+    u64::try_from(power).ok()
+}
+
+// Exponential moving average step: weights the new `sample` by `alpha_bps` and the `previous`
+// average by the rest. Returns None for an alpha above 100%.
+pub fn ema(previous: u64, sample: u64, alpha_bps: u64) -> Option<u64> {
+    let alpha = alpha_bps as u128;
+    if alpha > BPS_DENOMINATOR {
+        return None;
+    }
+    let weighted = (sample as u128)
+        .checked_mul(alpha)?
+        .checked_add((previous as u128).checked_mul(BPS_DENOMINATOR - alpha)?)?;
+    u64::try_from(weighted / BPS_DENOMINATOR).ok()
+}
+
+// Whether `part` makes up at least `threshold_bps` of `whole`.
+// An empty `whole` only satisfies a zero threshold.
+pub fn meets_threshold(part: u64, whole: u64, threshold_bps: u64) -> bool {
+    if whole == 0 {
+        return threshold_bps == 0;
+    }
+    (part as u128) * BPS_DENOMINATOR >= (whole as u128) * (threshold_bps as u128)
+}
+
+// Index of the option with strictly the most votes.
+// Returns None when no votes were cast or the top options are tied.
+pub fn leading_option(votes: &[u64]) -> Option<usize> {
+    let mut leader: Option<usize> = None;
+    let mut leading_votes: u64 = 0;
+    let mut tied = false;
+    for (index, &option_votes) in votes.iter().enumerate() {
+        if option_votes > leading_votes {
+            leader = Some(index);
+            leading_votes = option_votes;
+            tied = false;
+        } else if option_votes == leading_votes && option_votes > 0 {
+            tied = true;
+        }
+    }
+    if tied { None } else { leader }
+}
+
+// Split `weight` evenly across `choices` selected options, giving the rounding remainder to the first.
+// Returns an empty split when nothing was selected.
+pub fn split_vote_weight(weight: u64, choices: usize) -> Vec<u64> {
+    if choices == 0 {
+        return Vec::new();
+    }
+    let share = weight / choices as u64;
+    let mut shares = vec![share; choices];
+    shares[0] += weight % choices as u64;
+    shares
+}
+
+// Instant-runoff winner over ranked ballots, each a ranking of option indices (most preferred first)
+// and its weight. Every round counts each ballot for its highest-ranked option still standing; an option
+// with a strict majority of those continuing votes wins, otherwise the option with the fewest votes is
+// eliminated (ties eliminate the higher index). Returns the winner, its final-round votes and the
+// final-round continuing total, or None when no ballot counts or the last two options tie.
+pub fn instant_runoff(option_count: usize, ballots: &[(&[u8], u64)]) -> Option<(usize, u64, u64)> {
+    let mut eliminated = vec![false; option_count];
+    loop {
+        let mut counts = vec![0u64; option_count];
+        let mut continuing: u64 = 0;
+        for (ranking, weight) in ballots.iter() {
+            let choice = ranking
+                .iter()
+                .map(|choice| *choice as usize)
+                .find(|choice| *choice < option_count && !eliminated[*choice]);
+            if let Some(choice) = choice {
+                counts[choice] = counts[choice].saturating_add(*weight);
+                continuing = continuing.saturating_add(*weight);
+            }
+        }
+        if continuing == 0 {
+            return None;
+        }
+
+        let remaining: Vec<usize> = (0..option_count).filter(|option| !eliminated[*option]).collect();
+        let leader = remaining.iter().copied().max_by_key(|option| (counts[*option], std::cmp::Reverse(*option)))?;
+        if (counts[leader] as u128) * 2 > continuing as u128 {
+            return Some((leader, counts[leader], continuing));
+        }
+        // Two options left without a majority means they tied
+        if remaining.len() <= 2 {
+            return None;
+        }
+        let loser = remaining.iter().copied().min_by_key(|option| (counts[*option], std::cmp::Reverse(*option)))?;
+        eliminated[loser] = true;
+    }
+}
@@ -0,0 +1,366 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::{
+    AgentPerformance, AiAgent, Evaluator, PlatformConfig, Proposal, ProposalAction, ProposalStatus, ScoreReport,
+    MAX_PERFORMANCE_SCORE,
+};
+use crate::events::{PerformanceScoreUpdated, ProposalExecuted};
+use crate::error::SoreinError;
+use crate::governance::check_ready_for_completion;
+
+/// Size of the ed25519 program's per-signature offsets block.
+const ED25519_OFFSETS_SIZE: usize = 14;
+/// Offset of the first offsets block, after the signature count and a padding byte.
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Mirror of the ai_data_feed oracle's `AiDataFeedData` account layout, which lives in a separate program.
+/// Only `data` and `is_initialized` are read; the other fields are kept for the layout.
+#[derive(AnchorDeserialize)]
+#[allow(dead_code)]
+struct AiDataFeedData {
+    owner: Pubkey,
+    update_authority: Pubkey,
+    description: String,
+    metadata: String,
+    data: Vec<u8>,
+    last_updated: i64,
+    is_initialized: bool,
+}
+
+/// Context for the admin to point score updates at an ai_data_feed account.
+/// Once governance is enabled the feed is changed through SetScoreFeed proposals instead.
+#[derive(Accounts)]
+pub struct SetScoreFeed<'info> {
+    /// The platform administrator.
+    pub admin: Signer<'info>,
+    /// The platform configuration account holding the score feed.
+    #[account(
+        mut,
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        has_one = admin @ SoreinError::UnauthorizedAdmin,
+        constraint = !platform_config.governance_enabled @ SoreinError::GovernanceControlled
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Instruction to set the ai_data_feed account whose reports are accepted, and the program that must own it.
+/// The default key disables the feed.
+pub fn set_score_feed(ctx: Context<SetScoreFeed>, score_feed: Pubkey, score_feed_program: Pubkey) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.score_feed = score_feed;
+    platform_config.score_feed_program = score_feed_program;
+
+    msg!("Score feed set to {}", score_feed);
+    Ok(())
+}
+
+/// Context for the admin to register an evaluator key.
+/// Once governance is enabled evaluators are registered through RegisterEvaluator proposals instead.
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct RegisterEvaluator<'info> {
+    /// The platform administrator, who pays for the evaluator account.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// The platform configuration account holding the admin key.
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        has_one = admin @ SoreinError::UnauthorizedAdmin,
+        constraint = !platform_config.governance_enabled @ SoreinError::GovernanceControlled
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The evaluator account to be initialized.
+    #[account(
+        init,
+        payer = admin,
+        space = Evaluator::SPACE,
+        seeds = [b"evaluator", key.as_ref()],
+        bump
+    )]
+    pub evaluator: Account<'info, Evaluator>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to allow `key` to sign performance score attestations.
+pub fn register_evaluator(ctx: Context<RegisterEvaluator>, key: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    let evaluator = &mut ctx.accounts.evaluator;
+    evaluator.key = key;
+    evaluator.registered_at = clock.unix_timestamp;
+    evaluator.bump = ctx.bumps.evaluator;
+
+    msg!("Evaluator {} registered", key);
+    Ok(())
+}
+
+/// Context for the admin to remove an evaluator key.
+/// Once governance is enabled evaluators are removed through RemoveEvaluator proposals instead.
+#[derive(Accounts)]
+pub struct RemoveEvaluator<'info> {
+    /// The platform administrator, who receives the account's rent.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// The platform configuration account holding the admin key.
+    #[account(
+        seeds = [b"platform-config"],
+        bump = platform_config.bump,
+        has_one = admin @ SoreinError::UnauthorizedAdmin,
+        constraint = !platform_config.governance_enabled @ SoreinError::GovernanceControlled
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The evaluator account to be closed.
+    #[account(mut, close = admin, seeds = [b"evaluator", evaluator.key.as_ref()], bump = evaluator.bump)]
+    pub evaluator: Account<'info, Evaluator>,
+}
+
+/// Instruction to revoke an evaluator key. Scores it already reported are kept.
+pub fn remove_evaluator(ctx: Context<RemoveEvaluator>) -> Result<()> {
+    msg!("Evaluator {} removed", ctx.accounts.evaluator.key);
+    Ok(())
+}
+
+/// Context for executing a queued RegisterEvaluator proposal.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, key: Pubkey)]
+pub struct ExecuteRegisterEvaluator<'info> {
+    /// The caller who executes the proposal and pays for the evaluator account.
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    /// The platform configuration account holding the execution grace period.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal naming the evaluator key.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The evaluator account to be initialized.
+    #[account(
+        init,
+        payer = executor,
+        space = Evaluator::SPACE,
+        seeds = [b"evaluator", key.as_ref()],
+        bump
+    )]
+    pub evaluator: Account<'info, Evaluator>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to register the evaluator key named by a queued RegisterEvaluator proposal.
+pub fn execute_register_evaluator(ctx: Context<ExecuteRegisterEvaluator>, proposal_id: u64, key: Pubkey) -> Result<()> {
+    let action = check_evaluator_action(&ctx.accounts.proposal, &ctx.accounts.platform_config, proposal_id)?;
+    if action != (ProposalAction::RegisterEvaluator { key }) {
+        return err!(SoreinError::ProposalNotExecutable);
+    }
+
+    let clock = Clock::get()?;
+    let evaluator = &mut ctx.accounts.evaluator;
+    evaluator.key = key;
+    evaluator.registered_at = clock.unix_timestamp;
+    evaluator.bump = ctx.bumps.evaluator;
+
+    complete_evaluator_proposal(&mut ctx.accounts.proposal, ctx.accounts.executor.key(), action, clock.unix_timestamp);
+    msg!("Evaluator {} registered", key);
+    Ok(())
+}
+
+/// Context for executing a queued RemoveEvaluator proposal.
+#[derive(Accounts)]
+pub struct ExecuteRemoveEvaluator<'info> {
+    /// The caller who executes the proposal and receives the evaluator account's rent.
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    /// The platform configuration account holding the execution grace period.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal naming the evaluator key.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The evaluator account to be closed.
+    #[account(mut, close = executor, seeds = [b"evaluator", evaluator.key.as_ref()], bump = evaluator.bump)]
+    pub evaluator: Account<'info, Evaluator>,
+}
+
+/// Instruction to revoke the evaluator key named by a queued RemoveEvaluator proposal.
+pub fn execute_remove_evaluator(ctx: Context<ExecuteRemoveEvaluator>, proposal_id: u64) -> Result<()> {
+    let action = check_evaluator_action(&ctx.accounts.proposal, &ctx.accounts.platform_config, proposal_id)?;
+    let key = ctx.accounts.evaluator.key;
+    if action != (ProposalAction::RemoveEvaluator { key }) {
+        return err!(SoreinError::ProposalNotExecutable);
+    }
+
+    let clock = Clock::get()?;
+    complete_evaluator_proposal(&mut ctx.accounts.proposal, ctx.accounts.executor.key(), action, clock.unix_timestamp);
+    msg!("Evaluator {} removed", key);
+    Ok(())
+}
+
+/// Checks that the proposal is ready to execute and returns its action.
+fn check_evaluator_action(proposal: &Proposal, platform_config: &PlatformConfig, proposal_id: u64) -> Result<ProposalAction> {
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_ready_for_completion(proposal, platform_config)?;
+    proposal.action.ok_or_else(|| error!(SoreinError::ProposalNotExecutable))
+}
+
+/// Marks an evaluator proposal executed.
+fn complete_evaluator_proposal(proposal: &mut Proposal, executor: Pubkey, action: ProposalAction, now: i64) {
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = now;
+
+    emit!(ProposalExecuted {
+        proposal_id: proposal.id,
+        executor,
+        timestamp: now,
+        action: Some(action),
+    });
+}
+
+/// Context for recording a performance score report for an AI agent.
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct UpdatePerformanceScore<'info> {
+    /// The caller submitting the report, who pays for the history account on the first update.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The platform configuration account holding the score feed.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The agent being scored.
+    #[account(mut, seeds = [b"ai-agent".as_ref(), &agent_id.to_le_bytes()], bump = ai_agent.bump)]
+    pub ai_agent: Account<'info, AiAgent>,
+    /// The agent's rolling score history.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AgentPerformance::SPACE,
+        seeds = [b"agent-performance".as_ref(), &agent_id.to_le_bytes()],
+        bump
+    )]
+    pub performance: Account<'info, AgentPerformance>,
+    /// CHECK: the configured ai_data_feed account; its address and layout are checked in the handler.
+    /// Pass this to take the report from the feed's data.
+    pub score_feed: Option<UncheckedAccount<'info>>,
+    /// The registered evaluator whose ed25519 attestation precedes this instruction.
+    /// Pass this (with the instructions sysvar) to take the report from the attestation.
+    #[account(seeds = [b"evaluator", evaluator.key.as_ref()], bump = evaluator.bump)]
+    pub evaluator: Option<Account<'info, Evaluator>>,
+    /// CHECK: the instructions sysvar, used to read the ed25519 attestation.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to fold a score report into an agent's performance history. The report is read from the
+/// configured score feed, or from an ed25519 attestation by a registered evaluator placed immediately
+/// before this instruction; a caller-supplied score is never trusted.
+pub fn update_performance_score(ctx: Context<UpdatePerformanceScore>, agent_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let (report, source) = match (&ctx.accounts.score_feed, &ctx.accounts.evaluator, &ctx.accounts.instructions_sysvar) {
+        (Some(score_feed), None, None) => {
+            let feed = score_feed.to_account_info();
+            (read_feed_report(&feed, &ctx.accounts.platform_config)?, feed.key())
+        }
+        (None, Some(evaluator), Some(instructions_sysvar)) => (
+            read_attested_report(&instructions_sysvar.to_account_info(), evaluator)?,
+            evaluator.key,
+        ),
+        _ => return err!(SoreinError::UnauthorizedScoreSource),
+    };
+
+    if report.agent_id != agent_id || report.score > MAX_PERFORMANCE_SCORE || report.observed_at > clock.unix_timestamp {
+        return err!(SoreinError::InvalidScoreReport);
+    }
+
+    let performance = &mut ctx.accounts.performance;
+    if performance.sample_count == 0 {
+        performance.init(agent_id, ctx.bumps.performance);
+    } else if report.observed_at <= performance.last_observed_at() {
+        return err!(SoreinError::StaleScoreReport);
+    }
+    let ema_score = performance.record(&report, source)?;
+    ctx.accounts.ai_agent.performance_score = ema_score;
+
+    emit!(PerformanceScoreUpdated {
+        agent_id,
+        score: report.score,
+        ema_score,
+        source,
+        observed_at: report.observed_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Reads the score report carried at the start of the configured ai_data_feed account's data.
+/// The account must be owned by the configured ai_data_feed program.
+fn read_feed_report(feed: &AccountInfo, platform_config: &PlatformConfig) -> Result<ScoreReport> {
+    if platform_config.score_feed == Pubkey::default() || feed.key() != platform_config.score_feed {
+        return err!(SoreinError::UnauthorizedScoreSource);
+    }
+    // The layout check alone doesn't stop a look-alike account owned by another program
+    if feed.owner != &platform_config.score_feed_program {
+        return err!(SoreinError::InvalidAccount);
+    }
+    let account_data = feed.try_borrow_data()?;
+    let discriminator = &hash(b"account:AiDataFeedData").to_bytes()[..8];
+    if account_data.len() < 8 || &account_data[..8] != discriminator {
+        return err!(SoreinError::InvalidAccount);
+    }
+    let feed_data = AiDataFeedData::deserialize(&mut &account_data[8..])
+        .map_err(|_| error!(SoreinError::InvalidAccount))?;
+    if !feed_data.is_initialized {
+        return err!(SoreinError::InvalidAccount);
+    }
+    let mut payload = feed_data.data.get(..ScoreReport::SPACE).ok_or(SoreinError::InvalidScoreReport)?;
+    ScoreReport::deserialize(&mut payload).map_err(|_| error!(SoreinError::InvalidScoreReport))
+}
+
+/// Reads the score report signed by `evaluator` in the ed25519 program instruction immediately
+/// before the current one. The ed25519 program has already verified the signature by the time
+/// this runs; here we only check which key signed which message.
+fn read_attested_report(instructions_sysvar: &AccountInfo, evaluator: &Evaluator) -> Result<ScoreReport> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let attestation_index = current_index.checked_sub(1).ok_or(SoreinError::InvalidAttestation)?;
+    let attestation = load_instruction_at_checked(attestation_index as usize, instructions_sysvar)?;
+    if attestation.program_id != ed25519_program::ID {
+        return err!(SoreinError::InvalidAttestation);
+    }
+
+    let data = &attestation.data;
+    // Exactly one signature, with its key and message inside this same instruction
+    if data.len() < ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE || data[0] != 1 {
+        return err!(SoreinError::InvalidAttestation);
+    }
+    let offsets = &data[ED25519_OFFSETS_START..ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+    if [signature_instruction_index, public_key_instruction_index, message_instruction_index]
+        .iter()
+        .any(|index| *index != u16::MAX)
+    {
+        return err!(SoreinError::InvalidAttestation);
+    }
+
+    let public_key = data.get(public_key_offset..public_key_offset + 32).ok_or(SoreinError::InvalidAttestation)?;
+    if public_key != evaluator.key.as_ref() {
+        return err!(SoreinError::UnauthorizedScoreSource);
+    }
+    if message_size != ScoreReport::SPACE {
+        return err!(SoreinError::InvalidScoreReport);
+    }
+    let mut message = data.get(message_offset..message_offset + message_size).ok_or(SoreinError::InvalidAttestation)?;
+    ScoreReport::deserialize(&mut message).map_err(|_| error!(SoreinError::InvalidScoreReport))
+}
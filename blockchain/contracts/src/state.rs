@@ -1,9 +1,280 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::error::SoreinError;
+use crate::instructions::{validate_lockup, validate_thresholds, validate_timelock};
+use crate::math;
 
 // Constants for maximum sizes to prevent excessive memory allocation
 pub const MAX_NAME_LENGTH: usize = 32;
 pub const MAX_DESCRIPTION_LENGTH: usize = 256;
+pub const MAX_URI_LENGTH: usize = 200; // Metaplex metadata URI limit
+pub const AGENT_NFT_SYMBOL: &str = "AGENT";
+pub const MAX_PERFORMANCE_SCORE: u64 = 10_000; // Scores are reported in basis points (evaluate.py metrics x 10_000)
+pub const PERFORMANCE_EMA_ALPHA_BPS: u64 = 2_000; // Each new sample moves the average 20% of the way
+pub const MAX_PERFORMANCE_SAMPLES: usize = 10;
 pub const MAX_AGENTS_PER_USER: usize = 10;
+pub const MAX_PENDING_WITHDRAWALS: usize = 8;
+pub const MAX_LOCKUP_TIERS: usize = 4;
+pub const MAX_DELEGATORS: u8 = 5;
+pub const MAX_STAKE_CHECKPOINTS: usize = 32;
+pub const MAX_PROPOSAL_TITLE_LENGTH: usize = 100;
+pub const MAX_PROPOSAL_DESCRIPTION_LENGTH: usize = 1000;
+pub const MAX_PROPOSAL_OPTIONS: usize = 10;
+pub const MAX_OPTION_LENGTH: usize = 32;
+pub const MAX_RANKED_PREFERENCES: usize = 3;
+// One weight slot per possible ranking of up to MAX_RANKED_PREFERENCES options
+pub const RANKED_BALLOT_SLOTS: usize = ranking_slot_offset(MAX_RANKED_PREFERENCES + 1);
+pub const MAX_PROPOSAL_INSTRUCTIONS: u16 = 8;
+pub const MAX_PROPOSAL_SIGNATORIES: u8 = 5;
+pub const PROPOSAL_EXPIRATION_PERIOD: i64 = 2_592_000; // 30 days, from lifecycle.expiration in governance_config.json
+pub const MAX_INSTRUCTION_ACCOUNTS: usize = 16;
+pub const MAX_INSTRUCTION_DATA_LENGTH: usize = 1024;
+
+// Treasury settings, matching the treasury section of governance_config.json
+pub const TREASURY_EPOCH_DURATION: i64 = 2_592_000; // 30 days between budget resets
+pub const MAX_TREASURY_SPEND_BPS: u64 = 1_000; // 10% of the vault per proposal
+
+// Governance rewards are budgeted per epoch, matching rewards.distribution.frequency in governance_config.json
+pub const GOVERNANCE_REWARD_EPOCH_DURATION: i64 = 604_800; // 7 days
+// Proposals with votes stay open for this long after their reward epoch ends, so voters can still claim
+pub const GOVERNANCE_REWARD_CLAIM_PERIOD: i64 = 2_592_000; // 30 days
+
+// Council settings, matching the council section of governance_config.json
+pub const MAX_COUNCIL_MEMBERS: usize = 7;
+pub const MIN_COUNCIL_MEMBERS: usize = 3;
+pub const COUNCIL_TERM_DURATION: i64 = 31_536_000; // 1 year
+
+// Default lockup settings, matching the lockup section of governance_config.json
+pub const DEFAULT_MIN_LOCKUP_PERIOD: i64 = 604_800; // 7 days
+pub const DEFAULT_MAX_LOCKUP_PERIOD: i64 = 31_536_000; // 365 days
+pub const DEFAULT_MAX_LOCKUP_MULTIPLIER_BPS: u64 = 30_000; // 3.0x
+pub const DEFAULT_LOCKUP_TIERS: [LockupTier; MAX_LOCKUP_TIERS] = [
+    LockupTier { period: 604_800, multiplier_bps: 10_000 }, // 7 days, 1.0x
+    LockupTier { period: 2_592_000, multiplier_bps: 15_000 }, // 30 days, 1.5x
+    LockupTier { period: 7_776_000, multiplier_bps: 20_000 }, // 90 days, 2.0x
+    LockupTier { period: 31_536_000, multiplier_bps: 30_000 }, // 365 days, 3.0x
+];
+
+// How the lockup bonus is derived from the remaining lock time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum LockupMode {
+    // Bonus scales continuously with remaining time up to max_lockup_multiplier_bps
+    #[default]
+    Linear,
+    // Bonus steps through lockup_tiers
+    Tiered,
+}
+
+// A lockup tier: locks with at least `period` seconds remaining get `multiplier_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct LockupTier {
+    // Minimum remaining lock time for the tier (0 = unused slot)
+    pub period: i64,
+    // Voting power multiplier in basis points (10000 = 1.0x)
+    pub multiplier_bps: u64,
+}
+
+impl LockupTier {
+    pub const SPACE: usize = 8 + // period (i64)
+        8; // multiplier_bps (u64)
+}
+
+// Default voting thresholds, matching the thresholds section of governance_config.json
+pub const DEFAULT_APPROVAL_BPS: u64 = 6_000; // 60%
+pub const DEFAULT_QUORUM_BPS: u64 = 1_000; // 10%
+pub const DEFAULT_VETO_BPS: u64 = 3_330; // 33.3%
+
+// Default timelock settings, matching the execution section of governance_config.json
+pub const DEFAULT_EXECUTION_DELAY: i64 = 86_400; // 1 day
+pub const DEFAULT_CRITICAL_EXECUTION_DELAY: i64 = 172_800; // 2 days
+pub const DEFAULT_EXECUTION_GRACE_PERIOD: i64 = 2_592_000; // 30 days
+
+// Default voting period bounds, matching voting.duration in governance_config.json
+pub const DEFAULT_MIN_VOTING_DURATION: u64 = 259_200; // 3 days
+pub const DEFAULT_MAX_VOTING_DURATION: u64 = 1_209_600; // 14 days
+
+// Vote option index reserved for veto votes
+pub const VETO_VOTE_OPTION: u8 = u8::MAX;
+// Option that must win for a proposal's action to be executed
+pub const APPROVE_OPTION_INDEX: u8 = 0;
+
+// Thresholds a proposal must clear at finalization (all in basis points)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct GovernanceThresholds {
+    // Share of non-veto votes the leading option needs to pass
+    pub approval_bps: u64,
+    // Share of the total voting supply that must vote (including veto votes)
+    pub quorum_bps: u64,
+    // Share of all votes cast as veto that rejects the proposal (0 = veto disabled)
+    pub veto_bps: u64,
+}
+
+impl GovernanceThresholds {
+    pub const DEFAULT: GovernanceThresholds = GovernanceThresholds {
+        approval_bps: DEFAULT_APPROVAL_BPS,
+        quorum_bps: DEFAULT_QUORUM_BPS,
+        veto_bps: DEFAULT_VETO_BPS,
+    };
+
+    pub const SPACE: usize = 8 + // approval_bps (u64)
+        8 + // quorum_bps (u64)
+        8; // veto_bps (u64)
+}
+
+// Proposal categories, each of which can carry its own thresholds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ProposalType {
+    ProtocolUpgrade,
+    #[default]
+    ParameterChange,
+    TreasuryAllocation,
+    CommunityInitiative,
+    AiModelUpdate,
+}
+
+impl ProposalType {
+    pub const COUNT: usize = 5;
+
+    // Slot of this type in PlatformConfig::threshold_overrides
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    // Critical proposal types wait out the longer timelock before execution
+    pub fn is_critical(&self) -> bool {
+        matches!(self, ProposalType::ProtocolUpgrade | ProposalType::ParameterChange)
+    }
+}
+
+// How voters express preferences on a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum VoteType {
+    // One option receives the full vote weight
+    #[default]
+    SingleChoice,
+    // Up to max_choices options, with the vote weight split evenly between them
+    Approval { max_choices: u8 },
+    // Up to MAX_RANKED_PREFERENCES options ranked in order of preference, tallied by instant runoff
+    RankedChoice,
+}
+
+impl VoteType {
+    pub const SPACE: usize = 1 + // variant tag
+        1; // max_choices (u8)
+
+    // Whether the vote type can be used on a proposal with `option_count` options
+    pub fn is_valid_for(&self, option_count: usize) -> bool {
+        match *self {
+            VoteType::Approval { max_choices } => max_choices >= 1 && max_choices as usize <= option_count,
+            _ => true,
+        }
+    }
+
+    // Whether `choices` is a valid ballot: distinct option indices in a count the vote type allows,
+    // or VETO_VOTE_OPTION on its own
+    pub fn is_valid_ballot(&self, choices: &[u8], option_count: usize) -> bool {
+        if choices == [VETO_VOTE_OPTION] {
+            return true;
+        }
+        let distinct_options = choices.iter().enumerate().all(|(index, choice)| {
+            (*choice as usize) < option_count && !choices[..index].contains(choice)
+        });
+        let max_choices = match *self {
+            VoteType::SingleChoice => 1,
+            VoteType::Approval { max_choices } => max_choices as usize,
+            VoteType::RankedChoice => option_count.min(MAX_RANKED_PREFERENCES),
+        };
+        !choices.is_empty() && choices.len() <= max_choices && distinct_options
+    }
+}
+
+// A proposal's text and the options it is voted on, as passed to create_proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default, Debug)]
+pub struct ProposalContent {
+    pub title: String,
+    pub description: String,
+    pub options: Vec<String>,
+}
+
+// Lifecycle status of a governance proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ProposalStatus {
+    // Voting is open or has not been finalized yet
+    #[default]
+    Active,
+    // Quorum met, not vetoed and the leading option cleared the approval threshold
+    Approved,
+    // Quorum met but no option cleared the approval threshold, or the top options tied
+    Defeated,
+    // Veto votes reached the veto threshold, or the council vetoed it during the timelock
+    Vetoed,
+    // Too little of the voting supply took part
+    QuorumNotMet,
+    // Approved with an action, waiting out the timelock until its eta
+    Queued,
+    // Approved and its action has been applied
+    Executed,
+    // Cancelled by its creator or the guardian before execution
+    Cancelled,
+    // Being drafted; the creator can edit it and add signatories until it is signed off
+    Draft,
+    // Left as a draft or unfinalized for longer than PROPOSAL_EXPIRATION_PERIOD
+    Expired,
+    // Queued, but not executed before its execution grace period ended
+    ExecutionExpired,
+}
+
+// Platform change applied when an approved proposal is executed.
+// Treasury actions are paid out by the treasury execute instructions rather than execute_proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalAction {
+    SetRewardRate { reward_rate_bps: u64 },
+    SetMinStake { min_stake_amount: u64 },
+    SetEpochDuration { epoch_duration: i64 },
+    ToggleGovernance { enabled: bool },
+    TransferAdmin { new_admin: Pubkey },
+    SetGuardian { new_guardian: Pubkey },
+    TreasurySpend { mint: Pubkey, bucket: TreasuryBucket, recipient: Pubkey, amount: u64 },
+    SetTreasuryBudget { mint: Pubkey, bucket: TreasuryBucket, epoch_cap: u64 },
+    SetProposalRequirements {
+        proposal_deposit: u64,
+        min_stake_to_propose: u64,
+        min_voting_duration: u64,
+        max_voting_duration: u64,
+    },
+    // Only the first member_count entries of members are used
+    RotateCouncil { members: [Pubkey; MAX_COUNCIL_MEMBERS], member_count: u8, threshold: u8 },
+    SetGovernanceRewards { voting_reward_budget: u64, proposer_reward: u64, proposer_reward_epoch_cap: u64 },
+    SetUnstakeCooldown { unstake_cooldown: i64 },
+    // Unused tier slots are left zeroed, after the tiers in use
+    ConfigureLockup {
+        lockup_mode: LockupMode,
+        min_lockup_period: i64,
+        max_lockup_period: i64,
+        max_lockup_multiplier_bps: u64,
+        lockup_tiers: [LockupTier; MAX_LOCKUP_TIERS],
+    },
+    SetDefaultThresholds { thresholds: GovernanceThresholds },
+    SetThresholdOverride { proposal_type: ProposalType, thresholds: Option<GovernanceThresholds> },
+    ConfigureTimelock { execution_delay: i64, critical_execution_delay: i64, execution_grace_period: i64 },
+    SetScoreFeed { score_feed: Pubkey, score_feed_program: Pubkey },
+    // Evaluator actions create or close accounts, so they run through their own execute instructions
+    RegisterEvaluator { key: Pubkey },
+    RemoveEvaluator { key: Pubkey },
+}
+
+impl ProposalAction {
+    // Space for the largest variant (RotateCouncil)
+    pub const SPACE: usize = 1 + // variant tag
+        32 * MAX_COUNCIL_MEMBERS + // members (fixed array)
+        1 + // member_count (u8)
+        1; // threshold (u8)
+
+    // Whether the action moves or budgets treasury funds
+    pub fn is_treasury_action(&self) -> bool {
+        matches!(self, ProposalAction::TreasurySpend { .. } | ProposalAction::SetTreasuryBudget { .. })
+    }
+}
 
 // Global configuration account for the firoxy AI platform
 #[account]
@@ -21,22 +292,266 @@ pub struct PlatformConfig {
     pub last_reward_timestamp: i64,
     // Total staked amount across the platform
     pub total_staked: u64,
+    // Delay in seconds between requesting an unstake and being able to withdraw it
+    pub unstake_cooldown: i64,
+    // Global rewards accrued per staked token, scaled by REWARD_PRECISION
+    pub reward_per_share: u128,
+    // Mint of the token users stake on agents
+    pub stake_mint: Pubkey,
+    // Mint of the token rewards are paid in (may differ from stake_mint)
+    pub reward_mint: Pubkey,
+    // Program-owned token account holding staked tokens
+    pub staking_vault: Pubkey,
+    // Program-owned token account holding reward tokens
+    pub reward_vault: Pubkey,
+    // Whether governance proposals and voting are enabled
+    pub governance_enabled: bool,
+    // Number of proposals created (also the next proposal ID)
+    pub proposal_count: u64,
+    // How lockup time translates into a voting power multiplier
+    pub lockup_mode: LockupMode,
+    // Shortest lock a user can choose (in seconds)
+    pub min_lockup_period: i64,
+    // Longest lock a user can choose (in seconds)
+    pub max_lockup_period: i64,
+    // Multiplier reached at max_lockup_period remaining in linear mode (basis points)
+    pub max_lockup_multiplier_bps: u64,
+    // Multiplier tiers used in tiered mode
+    pub lockup_tiers: [LockupTier; MAX_LOCKUP_TIERS],
+    // Thresholds applied to proposals without a type-specific override
+    pub default_thresholds: GovernanceThresholds,
+    // Per-proposal-type threshold overrides, indexed by ProposalType
+    pub threshold_overrides: [Option<GovernanceThresholds>; ProposalType::COUNT],
+    // Delay in seconds between approval and earliest execution
+    pub execution_delay: i64,
+    // Delay in seconds applied instead for critical proposal types
+    pub critical_execution_delay: i64,
+    // Window in seconds after the eta during which a queued proposal can be executed
+    pub execution_grace_period: i64,
+    // Account allowed to cancel proposals before execution (defaults to the admin)
+    pub guardian: Pubkey,
+    // Stake tokens escrowed from the creator of each proposal, refunded or slashed at settlement
+    pub proposal_deposit: u64,
+    // Minimum staked amount required to create a proposal
+    pub min_stake_to_propose: u64,
+    // Shortest voting period a proposal can request
+    pub min_voting_duration: u64,
+    // Longest voting period a proposal can request
+    pub max_voting_duration: u64,
+    // Set by the council to halt staking operations in an emergency
+    pub paused: bool,
+    // Reward tokens shared pro-rata among voters in each governance reward epoch
+    pub voting_reward_budget: u64,
+    // Reward tokens paid to the creator of each passed proposal
+    pub proposer_reward: u64,
+    // Maximum total of proposer rewards paid for proposals closing in one governance reward epoch
+    pub proposer_reward_epoch_cap: u64,
+    // ai_data_feed account whose reports may update agent performance scores (default = none)
+    pub score_feed: Pubkey,
+    // Program that must own the score feed account (the ai_data_feed oracle program)
+    pub score_feed_program: Pubkey,
+    // Number of agents ever registered; the next agent gets this value as its ID
+    pub agent_count: u64,
     // Bump seed for PDA derivation
     pub bump: u8,
 }
 
 impl PlatformConfig {
     // Initialize the platform configuration with default values
-    pub fn init(&mut self, admin: Pubkey, reward_rate_bps: u64, min_stake_amount: u64, epoch_duration: i64, bump: u8) {
+    pub fn init(&mut self, admin: Pubkey, reward_rate_bps: u64, min_stake_amount: u64, epoch_duration: i64, unstake_cooldown: i64, bump: u8) {
         self.admin = admin;
         self.reward_rate_bps = reward_rate_bps;
         self.min_stake_amount = min_stake_amount;
         self.epoch_duration = epoch_duration;
         self.last_reward_timestamp = 0;
         self.total_staked = 0;
+        self.unstake_cooldown = unstake_cooldown;
+        self.reward_per_share = 0;
+        self.stake_mint = Pubkey::default();
+        self.reward_mint = Pubkey::default();
+        self.staking_vault = Pubkey::default();
+        self.reward_vault = Pubkey::default();
+        self.governance_enabled = false;
+        self.proposal_count = 0;
+        self.lockup_mode = LockupMode::Tiered;
+        self.min_lockup_period = DEFAULT_MIN_LOCKUP_PERIOD;
+        self.max_lockup_period = DEFAULT_MAX_LOCKUP_PERIOD;
+        self.max_lockup_multiplier_bps = DEFAULT_MAX_LOCKUP_MULTIPLIER_BPS;
+        self.lockup_tiers = DEFAULT_LOCKUP_TIERS;
+        self.default_thresholds = GovernanceThresholds::DEFAULT;
+        self.threshold_overrides = [None; ProposalType::COUNT];
+        self.execution_delay = DEFAULT_EXECUTION_DELAY;
+        self.critical_execution_delay = DEFAULT_CRITICAL_EXECUTION_DELAY;
+        self.execution_grace_period = DEFAULT_EXECUTION_GRACE_PERIOD;
+        self.guardian = admin;
+        self.proposal_deposit = 0;
+        self.min_stake_to_propose = 0;
+        self.min_voting_duration = DEFAULT_MIN_VOTING_DURATION;
+        self.max_voting_duration = DEFAULT_MAX_VOTING_DURATION;
+        self.paused = false;
+        self.voting_reward_budget = 0;
+        self.proposer_reward = 0;
+        self.proposer_reward_epoch_cap = 0;
+        self.score_feed = Pubkey::default();
+        self.score_feed_program = Pubkey::default();
+        self.agent_count = 0;
         self.bump = bump;
     }
 
+    // Apply an executed proposal's action to the platform configuration
+    pub fn apply_action(&mut self, action: &ProposalAction, now: i64) -> Result<()> {
+        match *action {
+            ProposalAction::SetRewardRate { reward_rate_bps } => {
+                // Accrue rewards at the old rate before the new rate takes effect
+                self.update_rewards(now)?;
+                self.reward_rate_bps = reward_rate_bps;
+            }
+            ProposalAction::SetMinStake { min_stake_amount } => {
+                self.min_stake_amount = min_stake_amount;
+            }
+            ProposalAction::SetEpochDuration { epoch_duration } => {
+                if epoch_duration <= 0 {
+                    return err!(SoreinError::InvalidProposalAction);
+                }
+                self.update_rewards(now)?;
+                self.epoch_duration = epoch_duration;
+            }
+            ProposalAction::ToggleGovernance { enabled } => {
+                self.governance_enabled = enabled;
+            }
+            ProposalAction::TransferAdmin { new_admin } => {
+                if new_admin == Pubkey::default() {
+                    return err!(SoreinError::InvalidProposalAction);
+                }
+                self.admin = new_admin;
+            }
+            ProposalAction::SetGuardian { new_guardian } => {
+                self.guardian = new_guardian;
+            }
+            ProposalAction::SetProposalRequirements {
+                proposal_deposit,
+                min_stake_to_propose,
+                min_voting_duration,
+                max_voting_duration,
+            } => {
+                if min_voting_duration == 0 || min_voting_duration > max_voting_duration {
+                    return err!(SoreinError::InvalidProposalAction);
+                }
+                self.proposal_deposit = proposal_deposit;
+                self.min_stake_to_propose = min_stake_to_propose;
+                self.min_voting_duration = min_voting_duration;
+                self.max_voting_duration = max_voting_duration;
+            }
+            ProposalAction::SetGovernanceRewards { voting_reward_budget, proposer_reward, proposer_reward_epoch_cap } => {
+                self.voting_reward_budget = voting_reward_budget;
+                self.proposer_reward = proposer_reward;
+                self.proposer_reward_epoch_cap = proposer_reward_epoch_cap;
+            }
+            ProposalAction::SetUnstakeCooldown { unstake_cooldown } => {
+                if unstake_cooldown < 0 {
+                    return err!(SoreinError::InvalidProposalAction);
+                }
+                self.unstake_cooldown = unstake_cooldown;
+            }
+            ProposalAction::ConfigureLockup {
+                lockup_mode,
+                min_lockup_period,
+                max_lockup_period,
+                max_lockup_multiplier_bps,
+                lockup_tiers,
+            } => {
+                let tier_count = lockup_tiers.iter().take_while(|tier| tier.period > 0).count();
+                if lockup_tiers[tier_count..].iter().any(|tier| *tier != LockupTier::default()) {
+                    return err!(SoreinError::InvalidProposalAction);
+                }
+                validate_lockup(min_lockup_period, max_lockup_period, max_lockup_multiplier_bps, &lockup_tiers[..tier_count])?;
+                self.lockup_mode = lockup_mode;
+                self.min_lockup_period = min_lockup_period;
+                self.max_lockup_period = max_lockup_period;
+                self.max_lockup_multiplier_bps = max_lockup_multiplier_bps;
+                self.lockup_tiers = lockup_tiers;
+            }
+            ProposalAction::SetDefaultThresholds { thresholds } => {
+                validate_thresholds(&thresholds)?;
+                self.default_thresholds = thresholds;
+            }
+            ProposalAction::SetThresholdOverride { proposal_type, thresholds } => {
+                if let Some(thresholds) = thresholds.as_ref() {
+                    validate_thresholds(thresholds)?;
+                }
+                self.threshold_overrides[proposal_type.index()] = thresholds;
+            }
+            ProposalAction::ConfigureTimelock { execution_delay, critical_execution_delay, execution_grace_period } => {
+                validate_timelock(execution_delay, critical_execution_delay, execution_grace_period)?;
+                self.execution_delay = execution_delay;
+                self.critical_execution_delay = critical_execution_delay;
+                self.execution_grace_period = execution_grace_period;
+            }
+            ProposalAction::SetScoreFeed { score_feed, score_feed_program } => {
+                self.score_feed = score_feed;
+                self.score_feed_program = score_feed_program;
+            }
+            ProposalAction::TreasurySpend { .. }
+            | ProposalAction::SetTreasuryBudget { .. }
+            | ProposalAction::RotateCouncil { .. }
+            | ProposalAction::RegisterEvaluator { .. }
+            | ProposalAction::RemoveEvaluator { .. } => {
+                return err!(SoreinError::UnsupportedProposalAction);
+            }
+        }
+        Ok(())
+    }
+
+    // Whether a proposal may ask for a voting period of `voting_duration` seconds
+    pub fn is_valid_voting_duration(&self, voting_duration: u64) -> bool {
+        voting_duration >= self.min_voting_duration && voting_duration <= self.max_voting_duration
+    }
+
+    // Timelock a proposal of the given type must wait between approval and execution
+    pub fn execution_delay_for(&self, proposal_type: ProposalType) -> i64 {
+        if proposal_type.is_critical() {
+            self.critical_execution_delay
+        } else {
+            self.execution_delay
+        }
+    }
+
+    // Thresholds that apply to a new proposal of the given type
+    pub fn thresholds_for(&self, proposal_type: ProposalType) -> GovernanceThresholds {
+        self.threshold_overrides[proposal_type.index()].unwrap_or(self.default_thresholds)
+    }
+
+    // Voting power multiplier for a lock with `remaining` seconds left
+    pub fn lockup_multiplier_bps(&self, remaining: i64) -> Result<u64> {
+        match self.lockup_mode {
+            LockupMode::Linear => math::linear_lockup_multiplier_bps(
+                remaining,
+                self.max_lockup_period,
+                self.max_lockup_multiplier_bps,
+            )
+            .ok_or(error!(SoreinError::ArithmeticError)),
+            LockupMode::Tiered => {
+                let tiers = self.lockup_tiers.map(|tier| (tier.period, tier.multiplier_bps));
+                Ok(math::tiered_lockup_multiplier_bps(remaining, &tiers))
+            }
+        }
+    }
+
+    // Accrue emissions since the last update into the global accumulator.
+    // Must be called before total_staked or the reward parameters change.
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        if now <= self.last_reward_timestamp {
+            return Ok(());
+        }
+        let elapsed = now - self.last_reward_timestamp;
+        let emission = math::emission_for_period(self.total_staked, self.reward_rate_bps, elapsed, self.epoch_duration)
+            .ok_or(SoreinError::ArithmeticError)?;
+        self.reward_per_share = math::accrue_reward_per_share(self.reward_per_share, emission, self.total_staked)
+            .ok_or(SoreinError::ArithmeticError)?;
+        self.last_reward_timestamp = now;
+        Ok(())
+    }
+
     // Calculate space required for the account
     pub const SPACE: usize = 8 + // discriminator
         32 + // admin (Pubkey)
@@ -45,72 +560,393 @@ impl PlatformConfig {
         8 + // epoch_duration (i64)
         8 + // last_reward_timestamp (i64)
         8 + // total_staked (u64)
+        8 + // unstake_cooldown (i64)
+        16 + // reward_per_share (u128)
+        32 + // stake_mint (Pubkey)
+        32 + // reward_mint (Pubkey)
+        32 + // staking_vault (Pubkey)
+        32 + // reward_vault (Pubkey)
+        1 + // governance_enabled (bool)
+        8 + // proposal_count (u64)
+        1 + // lockup_mode (enum)
+        8 + // min_lockup_period (i64)
+        8 + // max_lockup_period (i64)
+        8 + // max_lockup_multiplier_bps (u64)
+        LockupTier::SPACE * MAX_LOCKUP_TIERS + // lockup_tiers (fixed array)
+        GovernanceThresholds::SPACE + // default_thresholds
+        (1 + GovernanceThresholds::SPACE) * ProposalType::COUNT + // threshold_overrides (fixed array of Option)
+        8 + // execution_delay (i64)
+        8 + // critical_execution_delay (i64)
+        8 + // execution_grace_period (i64)
+        32 + // guardian (Pubkey)
+        8 + // proposal_deposit (u64)
+        8 + // min_stake_to_propose (u64)
+        8 + // min_voting_duration (u64)
+        8 + // max_voting_duration (u64)
+        1 + // paused (bool)
+        8 + // voting_reward_budget (u64)
+        8 + // proposer_reward (u64)
+        8 + // proposer_reward_epoch_cap (u64)
+        32 + // score_feed (Pubkey)
+        32 + // score_feed_program (Pubkey)
+        8 + // agent_count (u64)
         1; // bump (u8)
 }
 
+// Lifecycle status of an AI agent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum AgentStatus {
+    // Accepting stakes and earning rewards
+    #[default]
+    Active,
+    // Temporarily closed to new stakes; earns no rewards until resumed
+    Paused,
+    // Permanently closed to new stakes; stakers exit without cooldown until the owner closes it
+    Retired,
+}
+
 // AI Agent data structure to store agent-specific information
 #[account]
 #[derive(Default)]
 pub struct AiAgent {
     // Unique identifier for the agent
     pub agent_id: u64,
-    // Owner of the agent (user who registered it)
+    // Owner of the agent (the registrant, or whoever accepted a transfer).
+    // Once tokenized, the holder of nft_mint controls the agent instead and this is updated to
+    // the holder by sync_agent_owner.
     pub owner: Pubkey,
+    // Wallet proposed as the next owner (default = no transfer pending)
+    pub pending_owner: Pubkey,
+    // Mint of the agent's 1/1 NFT (default = not tokenized)
+    pub nft_mint: Pubkey,
     // Name of the AI agent (e.g., "Ontora-Alpha")
     pub name: String,
     // Description or metadata about the agent's purpose
     pub description: String,
+    // Metadata URI of the agent's NFT (empty if not tokenized)
+    pub uri: String,
     // Total amount staked on this agent
     pub staked_amount: u64,
-    // Performance score (e.g., based on accuracy or tasks completed)
+    // Performance score in basis points: the moving average of accepted score reports
     pub performance_score: u64,
     // Timestamp when the agent was registered
     pub created_at: i64,
+    // Rewards accrued per token staked on this agent, scaled by REWARD_PRECISION
+    pub reward_per_share: u128,
+    // Global accumulator value at the agent's last sync
+    pub reward_checkpoint: u128,
+    // Current lifecycle status
+    pub status: AgentStatus,
+    // Number of stake positions currently open on this agent
+    pub position_count: u64,
     // Bump seed for PDA derivation
     pub bump: u8,
 }
 
 impl AiAgent {
     // Initialize a new AI agent with provided data
-    pub fn init(&mut self, agent_id: u64, owner: Pubkey, name: String, description: String, created_at: i64, bump: u8) {
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(&mut self, agent_id: u64, owner: Pubkey, name: String, description: String, created_at: i64, reward_checkpoint: u128, bump: u8) {
         self.agent_id = agent_id;
         self.owner = owner;
+        self.pending_owner = Pubkey::default();
+        self.nft_mint = Pubkey::default();
         self.name = name;
         self.description = description;
+        self.uri = String::new();
         self.staked_amount = 0;
         self.performance_score = 0;
         self.created_at = created_at;
+        self.reward_per_share = 0;
+        self.reward_checkpoint = reward_checkpoint;
+        self.status = AgentStatus::Active;
+        self.position_count = 0;
         self.bump = bump;
     }
 
+    // Whether the agent has been minted as an NFT
+    pub fn is_tokenized(&self) -> bool {
+        self.nft_mint != Pubkey::default()
+    }
+
+    // Whether `owner` controls the agent: the recorded owner, or for a tokenized agent
+    // whoever holds its NFT in `nft_account`
+    pub fn is_owner(&self, owner: &Pubkey, nft_account: Option<&TokenAccount>) -> bool {
+        if !self.is_tokenized() {
+            return self.owner == *owner;
+        }
+        nft_account.is_some_and(|account| {
+            account.mint == self.nft_mint && account.owner == *owner && account.amount == 1
+        })
+    }
+
+    // Pull global accumulator growth since the last sync into the agent's accumulator.
+    // Growth while the agent is paused or retired is skipped, so its stakers earn nothing for it.
+    pub fn sync_rewards(&mut self, global_reward_per_share: u128) -> Result<()> {
+        if self.status == AgentStatus::Active {
+            self.reward_per_share = math::sync_agent_reward_per_share(
+                self.reward_per_share,
+                self.reward_checkpoint,
+                global_reward_per_share,
+            )
+            .ok_or(SoreinError::ArithmeticError)?;
+        }
+        self.reward_checkpoint = global_reward_per_share;
+        Ok(())
+    }
+
     // Calculate space required for the account
     pub const SPACE: usize = 8 + // discriminator
         8 + // agent_id (u64)
         32 + // owner (Pubkey)
+        32 + // pending_owner (Pubkey)
+        32 + // nft_mint (Pubkey)
         4 + MAX_NAME_LENGTH + // name (String with max length)
         4 + MAX_DESCRIPTION_LENGTH + // description (String with max length)
+        4 + MAX_URI_LENGTH + // uri (String with max length)
         8 + // staked_amount (u64)
         8 + // performance_score (u64)
         8 + // created_at (i64)
+        16 + // reward_per_share (u128)
+        16 + // reward_checkpoint (u128)
+        1 + // status (AgentStatus)
+        8 + // position_count (u64)
+        1; // bump (u8)
+}
+
+// Number of agents a wallet currently owns, capped at MAX_AGENTS_PER_USER (PDA seeded by owner)
+#[account]
+#[derive(Default)]
+pub struct OwnerAgents {
+    // Wallet owning the agents
+    pub owner: Pubkey,
+    // Number of agents owned and not yet closed
+    pub agent_count: u64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OwnerAgents {
+    // Initialize an owner with no agents
+    pub fn init(&mut self, owner: Pubkey, bump: u8) {
+        self.owner = owner;
+        self.agent_count = 0;
+        self.bump = bump;
+    }
+
+    // Count one more agent against the owner's limit
+    pub fn add_agent(&mut self) -> Result<()> {
+        if self.agent_count as usize >= MAX_AGENTS_PER_USER {
+            return err!(SoreinError::TooManyAgentsOwned);
+        }
+        self.agent_count += 1;
+        Ok(())
+    }
+
+    // Release one agent from the owner's limit
+    pub fn remove_agent(&mut self) -> Result<()> {
+        self.agent_count = self.agent_count.checked_sub(1).ok_or(SoreinError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // owner (Pubkey)
+        8 + // agent_count (u64)
+        1; // bump (u8)
+}
+
+// What is left of a closed AI agent while stakers still have positions on it (PDA seeded by agent_id).
+// A retired agent's accumulator no longer grows, so it is all a position needs to settle and exit.
+#[account]
+#[derive(Default)]
+pub struct ClosedAgent {
+    // ID of the closed agent
+    pub agent_id: u64,
+    // Owner who closed the agent and gets this account's rent back once the last position exits
+    pub owner: Pubkey,
+    // The agent's final rewards accrued per token staked, scaled by REWARD_PRECISION
+    pub reward_per_share: u128,
+    // Amount still staked on the agent
+    pub staked_amount: u64,
+    // Number of stake positions still open on the agent
+    pub position_count: u64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ClosedAgent {
+    // Record a retired agent's remaining positions as it is closed
+    pub fn init(&mut self, ai_agent: &AiAgent, owner: Pubkey, bump: u8) {
+        self.agent_id = ai_agent.agent_id;
+        self.owner = owner;
+        self.reward_per_share = ai_agent.reward_per_share;
+        self.staked_amount = ai_agent.staked_amount;
+        self.position_count = ai_agent.position_count;
+        self.bump = bump;
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // agent_id (u64)
+        32 + // owner (Pubkey)
+        16 + // reward_per_share (u128)
+        8 + // staked_amount (u64)
+        8 + // position_count (u64)
+        1; // bump (u8)
+}
+
+// A performance score report, carried in the score feed's data or signed by an evaluator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ScoreReport {
+    // Agent the score is for
+    pub agent_id: u64,
+    // Score in basis points, at most MAX_PERFORMANCE_SCORE
+    pub score: u64,
+    // Timestamp the evaluation was run at
+    pub observed_at: i64,
+}
+
+impl ScoreReport {
+    // Serialized size, which is also the exact length of a signed attestation message
+    pub const SPACE: usize = 8 + 8 + 8;
+}
+
+// A single accepted score report
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ScoreSample {
+    // Reported score in basis points
+    pub score: u64,
+    // Timestamp the evaluation was run at
+    pub observed_at: i64,
+    // Score feed account or evaluator key that reported it
+    pub source: Pubkey,
+}
+
+impl ScoreSample {
+    pub const SPACE: usize = 8 + 8 + 32;
+}
+
+// Rolling performance history of an AI agent (PDA seeded by agent_id)
+#[account]
+#[derive(Default)]
+pub struct AgentPerformance {
+    // Agent the history belongs to
+    pub agent_id: u64,
+    // Moving average of all accepted scores, mirrored into AiAgent.performance_score
+    pub ema_score: u64,
+    // Number of reports accepted so far
+    pub sample_count: u64,
+    // The most recent MAX_PERFORMANCE_SAMPLES reports, oldest first
+    pub samples: Vec<ScoreSample>,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl AgentPerformance {
+    // Initialize an empty history
+    pub fn init(&mut self, agent_id: u64, bump: u8) {
+        self.agent_id = agent_id;
+        self.ema_score = 0;
+        self.sample_count = 0;
+        self.samples = Vec::new();
+        self.bump = bump;
+    }
+
+    // Timestamp of the newest accepted report (0 if none)
+    pub fn last_observed_at(&self) -> i64 {
+        self.samples.last().map_or(0, |sample| sample.observed_at)
+    }
+
+    // Fold a report into the moving average and the sample window; the first report seeds the average
+    pub fn record(&mut self, report: &ScoreReport, source: Pubkey) -> Result<u64> {
+        self.ema_score = if self.sample_count == 0 {
+            report.score
+        } else {
+            math::ema(self.ema_score, report.score, PERFORMANCE_EMA_ALPHA_BPS).ok_or(SoreinError::ArithmeticError)?
+        };
+        self.sample_count = self.sample_count.checked_add(1).ok_or(SoreinError::ArithmeticError)?;
+        if self.samples.len() >= MAX_PERFORMANCE_SAMPLES {
+            self.samples.remove(0);
+        }
+        self.samples.push(ScoreSample {
+            score: report.score,
+            observed_at: report.observed_at,
+            source,
+        });
+        Ok(self.ema_score)
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // agent_id (u64)
+        8 + // ema_score (u64)
+        8 + // sample_count (u64)
+        4 + ScoreSample::SPACE * MAX_PERFORMANCE_SAMPLES + // samples (Vec with max length)
         1; // bump (u8)
 }
 
-// User staking data to track individual user balances and rewards
+// Key allowed to sign performance score attestations (PDA seeded by the key)
+#[account]
+#[derive(Default)]
+pub struct Evaluator {
+    // Ed25519 public key the evaluation pipeline signs with
+    pub key: Pubkey,
+    // Timestamp when the key was registered
+    pub registered_at: i64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Evaluator {
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // key (Pubkey)
+        8 + // registered_at (i64)
+        1; // bump (u8)
+}
+
+// An unstaked amount waiting for its cooldown to elapse before it can be withdrawn
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PendingWithdrawal {
+    // Agent the amount was unstaked from
+    pub agent_id: u64,
+    // Amount of tokens to be released
+    pub amount: u64,
+    // Timestamp after which the amount can be withdrawn
+    pub unlock_at: i64,
+}
+
+impl PendingWithdrawal {
+    pub const SPACE: usize = 8 + // agent_id (u64)
+        8 + // amount (u64)
+        8; // unlock_at (i64)
+}
+
+// User staking data aggregated across all of a user's agent positions
 #[account]
 #[derive(Default)]
 pub struct UserStake {
     // User public key (owner of this stake)
     pub user: Pubkey,
-    // Total amount staked by the user
+    // Total amount staked by the user across all positions
     pub staked_amount: u64,
-    // Accumulated rewards (unclaimed)
-    pub accumulated_rewards: u64,
-    // List of agent IDs the user has staked on
-    pub staked_agents: Vec<u64>,
+    // Number of agent positions the user has opened
+    pub position_count: u64,
     // Timestamp of the last stake update
     pub last_stake_update: i64,
-    // Timestamp of the last reward claim
-    pub last_reward_claim: i64,
+    // Unstaked amounts still in their cooldown window
+    pub pending_withdrawals: Vec<PendingWithdrawal>,
+    // Timestamp when the current lock was started (0 if never locked)
+    pub lockup_start: i64,
+    // Timestamp until which the stake cannot be unstaked
+    pub lockup_end: i64,
+    // User this stake's voting power is delegated to (default = not delegated)
+    pub delegate: Pubkey,
+    // Timestamp of the last delegate or undelegate by this user
+    pub delegation_updated_at: i64,
+    // Number of users currently delegating to this user
+    pub delegator_count: u8,
     // Bump seed for PDA derivation
     pub bump: u8,
 }
@@ -120,37 +956,984 @@ impl UserStake {
     pub fn init(&mut self, user: Pubkey, bump: u8) {
         self.user = user;
         self.staked_amount = 0;
-        self.accumulated_rewards = 0;
-        self.staked_agents = Vec::new();
+        self.position_count = 0;
         self.last_stake_update = 0;
-        self.last_reward_claim = 0;
+        self.pending_withdrawals = Vec::new();
+        self.lockup_start = 0;
+        self.lockup_end = 0;
+        self.delegate = Pubkey::default();
+        self.delegation_updated_at = 0;
+        self.delegator_count = 0;
         self.bump = bump;
     }
 
-    // Add an agent ID to the user's staked agents list
-    pub fn add_staked_agent(&mut self, agent_id: u64) -> Result<()> {
-        if self.staked_agents.len() >= MAX_AGENTS_PER_USER {
-            return err!(ErrorCode::TooManyAgents);
+    // Whether this stake's voting power is delegated to another user
+    pub fn is_delegated(&self) -> bool {
+        self.delegate != Pubkey::default()
+    }
+
+    // Whether the stake is still inside its lockup window
+    pub fn is_locked(&self, now: i64) -> bool {
+        now < self.lockup_end
+    }
+
+    // Lock the stake until `now + period`; an existing lock can be extended but never shortened
+    pub fn lock(&mut self, now: i64, period: i64) -> Result<()> {
+        let lockup_end = now.checked_add(period).ok_or(SoreinError::InvalidLockupPeriod)?;
+        if lockup_end < self.lockup_end {
+            return err!(SoreinError::InvalidLockupPeriod);
         }
-        if !self.staked_agents.contains(&agent_id) {
-            self.staked_agents.push(agent_id);
+        self.lockup_start = now;
+        self.lockup_end = lockup_end;
+        Ok(())
+    }
+
+    // Queue an unstaked amount for withdrawal once the cooldown has elapsed
+    pub fn queue_withdrawal(&mut self, agent_id: u64, amount: u64, unlock_at: i64) -> Result<()> {
+        if self.pending_withdrawals.len() >= MAX_PENDING_WITHDRAWALS {
+            return err!(SoreinError::TooManyPendingWithdrawals);
         }
+        self.pending_withdrawals.push(PendingWithdrawal {
+            agent_id,
+            amount,
+            unlock_at,
+        });
         Ok(())
     }
 
-    // Remove an agent ID from the user's staked agents list
-    pub fn remove_staked_agent(&mut self, agent_id: u64) {
-        self.staked_agents.retain(|&id| id != agent_id);
+    // Remove all matured withdrawals from the queue and return their total amount
+    pub fn take_unlocked_withdrawals(&mut self, now: i64) -> Result<u64> {
+        let mut total: u64 = 0;
+        for withdrawal in self.pending_withdrawals.iter().filter(|w| w.unlock_at <= now) {
+            total = total.checked_add(withdrawal.amount).ok_or(SoreinError::ArithmeticError)?;
+        }
+        self.pending_withdrawals.retain(|w| w.unlock_at > now);
+        Ok(total)
     }
 
     // Calculate space required for the account
     pub const SPACE: usize = 8 + // discriminator
         32 + // user (Pubkey)
         8 + // staked_amount (u64)
-        8 + // accumulated_rewards (u64)
-        4 + (8 * MAX_AGENTS_PER_USER) + // staked_agents (Vec<u64> with max length)
+        8 + // position_count (u64)
         8 + // last_stake_update (i64)
-        8 + // last_reward_claim (i64)
+        4 + (PendingWithdrawal::SPACE * MAX_PENDING_WITHDRAWALS) + // pending_withdrawals (Vec with max length)
+        8 + // lockup_start (i64)
+        8 + // lockup_end (i64)
+        32 + // delegate (Pubkey)
+        8 + // delegation_updated_at (i64)
+        1 + // delegator_count (u8)
+        1; // bump (u8)
+}
+
+// A user's aggregate stake and lock as of a slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct StakeCheckpoint {
+    // Slot at which the values took effect
+    pub slot: u64,
+    // Total amount staked by the user from this slot on
+    pub staked_amount: u64,
+    // Lockup end in effect from this slot on
+    pub lockup_end: i64,
+}
+
+impl StakeCheckpoint {
+    pub const SPACE: usize = 8 + // slot (u64)
+        8 + // staked_amount (u64)
+        8; // lockup_end (i64)
+}
+
+// History of a user's stake, used to compute voting power at a proposal's snapshot slot
+// (PDA seeded by user). Holds up to MAX_STAKE_CHECKPOINTS changes, oldest first; older ones are merged.
+#[account]
+#[derive(Default)]
+pub struct StakeCheckpoints {
+    // User whose stake is tracked
+    pub user: Pubkey,
+    // Checkpoints ordered by slot
+    pub checkpoints: Vec<StakeCheckpoint>,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl StakeCheckpoints {
+    // Initialize an empty checkpoint history
+    pub fn init(&mut self, user: Pubkey, bump: u8) {
+        self.user = user;
+        self.checkpoints = Vec::new();
+        self.bump = bump;
+    }
+
+    // Record the user's stake as of `slot`. Several changes in one slot collapse into one checkpoint.
+    // Once the history is full the two oldest checkpoints are merged into one at the older slot, holding the
+    // lower stake and the earlier lockup end, so a snapshot that old still sees the least the user held
+    // since: never more than they had, and never nothing when they were staked throughout.
+    pub fn record(&mut self, slot: u64, staked_amount: u64, lockup_end: i64) {
+        let checkpoint = StakeCheckpoint { slot, staked_amount, lockup_end };
+        if let Some(last) = self.checkpoints.last_mut() {
+            if last.slot == slot {
+                *last = checkpoint;
+                return;
+            }
+        }
+        if self.checkpoints.len() >= MAX_STAKE_CHECKPOINTS {
+            let merged = self.checkpoints.remove(1);
+            let oldest = &mut self.checkpoints[0];
+            oldest.staked_amount = oldest.staked_amount.min(merged.staked_amount);
+            oldest.lockup_end = oldest.lockup_end.min(merged.lockup_end);
+        }
+        self.checkpoints.push(checkpoint);
+    }
+
+    // Latest checkpoint at or before `slot`, if the history reaches back that far
+    pub fn at_or_before(&self, slot: u64) -> Option<&StakeCheckpoint> {
+        self.checkpoints.iter().rev().find(|checkpoint| checkpoint.slot <= slot)
+    }
+
+    // Raw stake at `snapshot_slot`, without any lockup multiplier
+    pub fn stake_at(&self, snapshot_slot: u64) -> u64 {
+        self.at_or_before(snapshot_slot).map_or(0, |checkpoint| checkpoint.staked_amount)
+    }
+
+    // Voting power at `snapshot_slot`, with the lockup multiplier measured at `reference_time`.
+    // Stake with no checkpoint at or before the snapshot has no voting power.
+    pub fn voting_power_at(&self, snapshot_slot: u64, reference_time: i64, platform_config: &PlatformConfig) -> Result<u64> {
+        let checkpoint = match self.at_or_before(snapshot_slot) {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(0),
+        };
+        let remaining = checkpoint.lockup_end.saturating_sub(reference_time);
+        let multiplier_bps = platform_config.lockup_multiplier_bps(remaining)?;
+        math::voting_power(checkpoint.staked_amount, multiplier_bps).ok_or(error!(SoreinError::ArithmeticError))
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // user (Pubkey)
+        4 + (StakeCheckpoint::SPACE * MAX_STAKE_CHECKPOINTS) + // checkpoints (Vec with max length)
+        1; // bump (u8)
+}
+
+// A single user's stake on a single AI agent (PDA seeded by user and agent ID)
+#[account]
+#[derive(Default)]
+pub struct StakePosition {
+    // User who owns this position
+    pub user: Pubkey,
+    // Agent the position is staked on
+    pub agent_id: u64,
+    // Amount currently staked on the agent
+    pub amount: u64,
+    // Timestamp when the position was first opened
+    pub staked_at: i64,
+    // Settled rewards not yet paid out
+    pub accumulated_rewards: u64,
+    // Agent accumulator checkpoint (amount * reward_per_share) at the last settlement
+    pub reward_debt: u128,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl StakePosition {
+    // Initialize a new position for the given user and agent
+    pub fn init(&mut self, user: Pubkey, agent_id: u64, staked_at: i64, bump: u8) {
+        self.user = user;
+        self.agent_id = agent_id;
+        self.amount = 0;
+        self.staked_at = staked_at;
+        self.accumulated_rewards = 0;
+        self.reward_debt = 0;
+        self.bump = bump;
+    }
+
+    // Move rewards earned since the last checkpoint into accumulated_rewards
+    pub fn settle_rewards(&mut self, agent_reward_per_share: u128) -> Result<()> {
+        let pending = math::pending_reward(self.amount, agent_reward_per_share, self.reward_debt)
+            .ok_or(SoreinError::ArithmeticError)?;
+        self.accumulated_rewards = self.accumulated_rewards
+            .checked_add(pending)
+            .ok_or(SoreinError::ArithmeticError)?;
+        self.reward_debt = math::reward_debt(self.amount, agent_reward_per_share)
+            .ok_or(SoreinError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Re-checkpoint after the staked amount changes; call only after settle_rewards
+    pub fn checkpoint_rewards(&mut self, agent_reward_per_share: u128) -> Result<()> {
+        self.reward_debt = math::reward_debt(self.amount, agent_reward_per_share)
+            .ok_or(SoreinError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // user (Pubkey)
+        8 + // agent_id (u64)
+        8 + // amount (u64)
+        8 + // staked_at (i64)
+        8 + // accumulated_rewards (u64)
+        16 + // reward_debt (u128)
+        1; // bump (u8)
+}
+
+// Governance proposal voted on with lockup-weighted stake (PDA seeded by proposal ID)
+#[account]
+#[derive(Default)]
+pub struct Proposal {
+    // Sequential proposal ID
+    pub id: u64,
+    // User who created the proposal
+    pub creator: Pubkey,
+    // Short title of the proposal
+    pub title: String,
+    // Full description of the proposal
+    pub description: String,
+    // Category of the proposal, which selects its thresholds
+    pub proposal_type: ProposalType,
+    // How votes are cast and tallied
+    pub vote_type: VoteType,
+    // Labels of the options being voted on
+    pub options: Vec<String>,
+    // Total voting power cast for each option
+    pub votes: Vec<u64>,
+    // Total voting power cast as veto
+    pub veto_votes: u64,
+    // Raw stake behind the votes cast (options and veto), counted against total_voting_supply for quorum
+    pub quorum_votes: u64,
+    // Thresholds snapshotted from the platform config at creation
+    pub thresholds: GovernanceThresholds,
+    // Total staked supply at sign-off, used as the quorum denominator against quorum_votes
+    pub total_voting_supply: u64,
+    // Slot before sign-off; voting power is read from stake checkpoints at or before it, so stake
+    // changed in the sign-off slot itself doesn't count
+    pub snapshot_slot: u64,
+    // Timestamp when the draft was created
+    pub created_at: i64,
+    // Length of the voting period, which starts at sign-off
+    pub voting_duration: u64,
+    // Number of co-signatories added to the draft
+    pub signatory_count: u8,
+    // Number of co-signatories that have signed off
+    pub signed_off_count: u8,
+    // Timestamp when voting opens (0 while a draft)
+    pub start_time: i64,
+    // Timestamp when voting closes
+    pub end_time: i64,
+    // Current lifecycle status
+    pub status: ProposalStatus,
+    // Option that won, set when the proposal is approved
+    pub winning_option: Option<u8>,
+    // Platform change applied on execution if the approve option wins
+    pub action: Option<ProposalAction>,
+    // Number of ProposalInstruction accounts attached to the proposal
+    pub instruction_count: u16,
+    // Number of attached instructions already invoked
+    pub executed_instruction_count: u16,
+    // Earliest execution timestamp once queued (0 if not queued)
+    pub eta: i64,
+    // Timestamp when the action was executed (0 if not executed)
+    pub executed_at: i64,
+    // Number of voters with a live vote record on this proposal
+    pub voter_count: u64,
+    // Deposit escrowed from the creator at creation
+    pub deposit_amount: u64,
+    // Whether the deposit has been refunded or slashed
+    pub deposit_settled: bool,
+    // Set by the guardian when the proposal is spam; its deposit is slashed
+    pub flagged_spam: bool,
+    // Set when the guardian cancels the proposal; its deposit is slashed
+    pub cancelled_by_guardian: bool,
+    // Passed-proposal reward snapshotted from the platform config at sign-off
+    pub proposer_reward: u64,
+    // Whether the creator has claimed the passed-proposal reward
+    pub proposer_reward_claimed: bool,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Proposal {
+    // Calculate space required for the account
+    pub const LEN: usize = 8 + // discriminator
+        8 + // id (u64)
+        32 + // creator (Pubkey)
+        4 + MAX_PROPOSAL_TITLE_LENGTH + // title (String with max length)
+        4 + MAX_PROPOSAL_DESCRIPTION_LENGTH + // description (String with max length)
+        1 + // proposal_type (enum)
+        VoteType::SPACE + // vote_type
+        4 + (4 + MAX_OPTION_LENGTH) * MAX_PROPOSAL_OPTIONS + // options (Vec of Strings with max length)
+        4 + 8 * MAX_PROPOSAL_OPTIONS + // votes (Vec<u64> with max length)
+        8 + // veto_votes (u64)
+        8 + // quorum_votes (u64)
+        GovernanceThresholds::SPACE + // thresholds
+        8 + // total_voting_supply (u64)
+        8 + // snapshot_slot (u64)
+        8 + // created_at (i64)
+        8 + // voting_duration (u64)
+        1 + // signatory_count (u8)
+        1 + // signed_off_count (u8)
+        8 + // start_time (i64)
+        8 + // end_time (i64)
+        1 + // status (enum)
+        2 + // winning_option (Option<u8>)
+        1 + ProposalAction::SPACE + // action (Option<ProposalAction>)
+        2 + // instruction_count (u16)
+        2 + // executed_instruction_count (u16)
+        8 + // eta (i64)
+        8 + // executed_at (i64)
+        8 + // voter_count (u64)
+        8 + // deposit_amount (u64)
+        1 + // deposit_settled (bool)
+        1 + // flagged_spam (bool)
+        1 + // cancelled_by_guardian (bool)
+        8 + // proposer_reward (u64)
+        1 + // proposer_reward_claimed (bool)
+        1; // bump (u8)
+
+    // Whether votes can currently be cast, changed or withdrawn
+    pub fn is_voting_open(&self, now: i64) -> bool {
+        self.status == ProposalStatus::Active && now >= self.start_time && now <= self.end_time
+    }
+
+    // Whether the proposal sat in draft, or went unfinalized after voting, for longer than the expiration period
+    pub fn is_expired(&self, now: i64) -> bool {
+        let expires_from = match self.status {
+            ProposalStatus::Draft => self.created_at,
+            ProposalStatus::Active => self.end_time,
+            _ => return false,
+        };
+        now > expires_from.saturating_add(PROPOSAL_EXPIRATION_PERIOD)
+    }
+
+    // Whether a queued proposal missed its execution window, which closes `grace_period` after its eta
+    pub fn is_execution_expired(&self, now: i64, grace_period: i64) -> bool {
+        self.status == ProposalStatus::Queued && now > self.eta.saturating_add(grace_period)
+    }
+
+    // Governance reward epoch the proposal's votes count towards (the epoch voting closes in)
+    pub fn reward_epoch(&self) -> u64 {
+        (self.end_time.max(0) / GOVERNANCE_REWARD_EPOCH_DURATION) as u64
+    }
+
+    // Whether the vote passed, whether or not its payload has run yet
+    pub fn has_passed(&self) -> bool {
+        matches!(self.status, ProposalStatus::Approved | ProposalStatus::Queued | ProposalStatus::Executed)
+    }
+
+    // Whether the proposal has reached a final status and its account can be closed
+    pub fn is_closable(&self) -> bool {
+        match self.status {
+            ProposalStatus::Draft | ProposalStatus::Active | ProposalStatus::Queued => false,
+            ProposalStatus::Approved => !self.has_payload(),
+            _ => true,
+        }
+    }
+
+    // Whether votes on the proposal earn voting rewards: it was tallied at finalization (not cancelled
+    // or expired) and the guardian didn't flag it as spam
+    pub fn is_reward_eligible(&self) -> bool {
+        let tallied = matches!(
+            self.status,
+            ProposalStatus::Approved
+                | ProposalStatus::Defeated
+                | ProposalStatus::Vetoed
+                | ProposalStatus::QuorumNotMet
+                | ProposalStatus::Queued
+                | ProposalStatus::Executed
+                | ProposalStatus::ExecutionExpired
+        );
+        tallied && !self.flagged_spam && !self.cancelled_by_guardian
+    }
+
+    // Earliest time the proposal can be closed without cutting short its voters' reward claims
+    pub fn reward_claims_end(&self) -> i64 {
+        if self.voter_count == 0 {
+            return 0;
+        }
+        let epoch_end = ((self.reward_epoch() as i64).saturating_add(1)).saturating_mul(GOVERNANCE_REWARD_EPOCH_DURATION);
+        epoch_end.saturating_add(GOVERNANCE_REWARD_CLAIM_PERIOD)
+    }
+
+    // Whether the proposal carries anything to execute once approved
+    pub fn has_payload(&self) -> bool {
+        self.action.is_some() || self.instruction_count > 0
+    }
+
+    // Whether the creator gets the deposit back: the guardian must not have flagged or cancelled the proposal,
+    // the council must not have vetoed it, and it must have been withdrawn by its creator (before voting opened
+    // or before any votes were cast) or reached quorum without being vetoed
+    pub fn deposit_refundable(&self) -> bool {
+        if self.flagged_spam || self.cancelled_by_guardian || self.status == ProposalStatus::Vetoed {
+            return false;
+        }
+        self.status == ProposalStatus::Cancelled || self.start_time == 0 || self.quorum_or_veto_failure().is_none()
+    }
+
+    // Total voting power cast for options (excluding veto)
+    fn option_votes(&self) -> u64 {
+        self.votes.iter().fold(0u64, |total, votes| total.saturating_add(*votes))
+    }
+
+    // QuorumNotMet or Vetoed if the vote failed before any option is considered.
+    // Quorum compares raw stake with raw stake, since lockup multipliers would inflate the turnout;
+    // the veto share compares lockup-weighted votes with each other.
+    fn quorum_or_veto_failure(&self) -> Option<ProposalStatus> {
+        if !math::meets_threshold(self.quorum_votes, self.total_voting_supply, self.thresholds.quorum_bps) {
+            return Some(ProposalStatus::QuorumNotMet);
+        }
+        let total_cast = self.option_votes().saturating_add(self.veto_votes);
+        if self.thresholds.veto_bps > 0 && math::meets_threshold(self.veto_votes, total_cast, self.thresholds.veto_bps) {
+            return Some(ProposalStatus::Vetoed);
+        }
+        None
+    }
+
+    // Outcome of the vote under the proposal's snapshotted thresholds.
+    // Quorum is checked first, then veto, then approval of the leading option. For ranked-choice
+    // proposals the leader is the instant-runoff winner from `ballot_box`, measured against the
+    // final-round votes.
+    pub fn tally(&self, ballot_box: Option<&BallotBox>) -> (ProposalStatus, Option<u8>) {
+        if let Some(status) = self.quorum_or_veto_failure() {
+            return (status, None);
+        }
+        // A tie between the top options is treated as a defeat
+        let leader = match self.vote_type {
+            VoteType::RankedChoice => ballot_box.and_then(|ballot_box| ballot_box.instant_runoff(self.options.len())),
+            _ => math::leading_option(&self.votes).map(|index| (index, self.votes[index], self.option_votes())),
+        };
+        match leader {
+            Some((index, votes, total)) if math::meets_threshold(votes, total, self.thresholds.approval_bps) => {
+                (ProposalStatus::Approved, Some(index as u8))
+            }
+            _ => (ProposalStatus::Defeated, None),
+        }
+    }
+}
+
+// Ballots of a ranked-choice proposal, kept for the instant-runoff tally (PDA seeded by proposal).
+// Every possible ranking has its own weight slot, so the box can't be filled up by distinct junk rankings.
+#[account]
+#[derive(Default)]
+pub struct BallotBox {
+    // Proposal the ballots were cast on
+    pub proposal: Pubkey,
+    // Combined voting power cast for each ranking, indexed by ranking_slot
+    pub weights: Vec<u64>,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl BallotBox {
+    // Initialize an empty ballot box for a proposal
+    pub fn init(&mut self, proposal: Pubkey, bump: u8) {
+        self.proposal = proposal;
+        self.weights = vec![0; RANKED_BALLOT_SLOTS];
+        self.bump = bump;
+    }
+
+    // Add a ranking's weight
+    pub fn add(&mut self, ranking: &[u8], weight: u64) -> Result<()> {
+        let slot = self.slot_mut(ranking)?;
+        *slot = slot.checked_add(weight).ok_or(SoreinError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Remove a previously added ranking's weight
+    pub fn remove(&mut self, ranking: &[u8], weight: u64) -> Result<()> {
+        let slot = self.slot_mut(ranking)?;
+        *slot = slot.checked_sub(weight).ok_or(SoreinError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Instant-runoff winner with its final-round votes and the final-round continuing total
+    pub fn instant_runoff(&self, option_count: usize) -> Option<(usize, u64, u64)> {
+        let rankings: Vec<(Vec<u8>, u64)> = self.weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0)
+            .map(|(slot, weight)| (slot_ranking(slot), *weight))
+            .collect();
+        let ballots: Vec<(&[u8], u64)> = rankings.iter().map(|(ranking, weight)| (ranking.as_slice(), *weight)).collect();
+        math::instant_runoff(option_count, &ballots)
+    }
+
+    fn slot_mut(&mut self, ranking: &[u8]) -> Result<&mut u64> {
+        let slot = ranking_slot(ranking).ok_or(SoreinError::InvalidBallot)?;
+        self.weights.get_mut(slot).ok_or_else(|| error!(SoreinError::InvalidBallot))
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // proposal (Pubkey)
+        4 + 8 * RANKED_BALLOT_SLOTS + // weights (Vec<u64> with one slot per ranking)
+        1; // bump (u8)
+}
+
+// Number of ranking slots a ranking of `len` preferences starts after: one per shorter ranking
+const fn ranking_slot_offset(len: usize) -> usize {
+    let mut offset = 0;
+    let mut rankings = 1;
+    let mut preferences = 1;
+    while preferences < len {
+        rankings *= MAX_PROPOSAL_OPTIONS;
+        offset += rankings;
+        preferences += 1;
+    }
+    offset
+}
+
+// Slot of a ranking of 1 to MAX_RANKED_PREFERENCES option indices: its length's offset plus the
+// ranking read as a base-MAX_PROPOSAL_OPTIONS number
+fn ranking_slot(ranking: &[u8]) -> Option<usize> {
+    if ranking.is_empty() || ranking.len() > MAX_RANKED_PREFERENCES {
+        return None;
+    }
+    let mut slot = 0;
+    for option in ranking {
+        if *option as usize >= MAX_PROPOSAL_OPTIONS {
+            return None;
+        }
+        slot = slot * MAX_PROPOSAL_OPTIONS + *option as usize;
+    }
+    Some(ranking_slot_offset(ranking.len()) + slot)
+}
+
+// Ranking stored in `slot`, the inverse of ranking_slot
+fn slot_ranking(slot: usize) -> Vec<u8> {
+    let len = (1..=MAX_RANKED_PREFERENCES)
+        .take_while(|len| ranking_slot_offset(*len) <= slot)
+        .last()
+        .unwrap_or(1);
+    let mut value = slot - ranking_slot_offset(len);
+    let mut ranking = vec![0; len];
+    for option in ranking.iter_mut().rev() {
+        *option = (value % MAX_PROPOSAL_OPTIONS) as u8;
+        value /= MAX_PROPOSAL_OPTIONS;
+    }
+    ranking
+}
+
+// Account meta of an instruction stored on a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ProposalAccountMeta {
+    // Account passed to the instruction
+    pub pubkey: Pubkey,
+    // Whether the account must sign (the governance authority signs via its PDA seeds)
+    pub is_signer: bool,
+    // Whether the account is writable
+    pub is_writable: bool,
+}
+
+impl ProposalAccountMeta {
+    pub const SPACE: usize = 32 + // pubkey (Pubkey)
+        1 + // is_signer (bool)
+        1; // is_writable (bool)
+}
+
+// An instruction the governance authority invokes when a proposal is executed
+// (PDA seeded by proposal and instruction index). Data can be appended in chunks.
+#[account]
+#[derive(Default)]
+pub struct ProposalInstruction {
+    // Proposal the instruction belongs to
+    pub proposal: Pubkey,
+    // Proposal creator, who paid for the account and is refunded when it is closed
+    pub creator: Pubkey,
+    // Position of the instruction within the proposal
+    pub index: u16,
+    // Program to invoke
+    pub program_id: Pubkey,
+    // Accounts passed to the program
+    pub accounts: Vec<ProposalAccountMeta>,
+    // Serialized instruction data
+    pub data: Vec<u8>,
+    // Whether the instruction has been invoked
+    pub executed: bool,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ProposalInstruction {
+    // Initialize an instruction with its accounts; data is appended separately
+    pub fn init(
+        &mut self,
+        proposal: Pubkey,
+        creator: Pubkey,
+        index: u16,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccountMeta>,
+        bump: u8,
+    ) {
+        self.proposal = proposal;
+        self.creator = creator;
+        self.index = index;
+        self.program_id = program_id;
+        self.accounts = accounts;
+        self.data = Vec::new();
+        self.executed = false;
+        self.bump = bump;
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // proposal (Pubkey)
+        32 + // creator (Pubkey)
+        2 + // index (u16)
+        32 + // program_id (Pubkey)
+        4 + (ProposalAccountMeta::SPACE * MAX_INSTRUCTION_ACCOUNTS) + // accounts (Vec with max length)
+        4 + MAX_INSTRUCTION_DATA_LENGTH + // data (Vec<u8> with max length)
+        1 + // executed (bool)
+        1; // bump (u8)
+}
+
+// A co-signatory of a draft proposal (PDA seeded by proposal and signatory)
+#[account]
+#[derive(Default)]
+pub struct SignatoryRecord {
+    // Proposal the signatory was added to
+    pub proposal: Pubkey,
+    // Proposal creator, who paid for the account and is refunded when it is closed
+    pub creator: Pubkey,
+    // Co-signatory who must sign off before voting opens
+    pub signatory: Pubkey,
+    // Whether the signatory has signed off
+    pub signed_off: bool,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl SignatoryRecord {
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // proposal (Pubkey)
+        32 + // creator (Pubkey)
+        32 + // signatory (Pubkey)
+        1 + // signed_off (bool)
+        1; // bump (u8)
+}
+
+// A single voter's vote on a proposal (PDA seeded by proposal and voter)
+#[account]
+#[derive(Default)]
+pub struct VoteRecord {
+    // Proposal the vote was cast on
+    pub proposal: Pubkey,
+    // Voter who cast the vote
+    pub voter: Pubkey,
+    // Options the voter chose, in order of preference for ranked-choice proposals
+    pub choices: Vec<u8>,
+    // Voting power counted for the choices
+    pub weight: u64,
+    // Raw stake behind the vote, counted towards the proposal's quorum
+    pub stake: u64,
+    // Timestamp of the latest vote or vote change
+    pub voted_at: i64,
+    // Governance reward epoch the vote counts towards
+    pub reward_epoch: u64,
+    // Largest weight the vote has had, which is what it counted towards the voter's reward epoch weight
+    pub reward_weight: u64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    // Initialize a vote record for a first-time vote
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        &mut self,
+        proposal: Pubkey,
+        voter: Pubkey,
+        choices: Vec<u8>,
+        weight: u64,
+        stake: u64,
+        voted_at: i64,
+        reward_epoch: u64,
+        bump: u8,
+    ) {
+        self.proposal = proposal;
+        self.voter = voter;
+        self.choices = choices;
+        self.weight = weight;
+        self.stake = stake;
+        self.voted_at = voted_at;
+        self.reward_epoch = reward_epoch;
+        self.reward_weight = weight;
+        self.bump = bump;
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // proposal (Pubkey)
+        32 + // voter (Pubkey)
+        4 + MAX_PROPOSAL_OPTIONS + // choices (Vec<u8> with max length)
+        8 + // weight (u64)
+        8 + // stake (u64)
+        8 + // voted_at (i64)
+        8 + // reward_epoch (u64)
+        8 + // reward_weight (u64)
+        1; // bump (u8)
+}
+
+// Voting reward budget and participation for one governance reward epoch (PDA seeded by epoch index).
+// Votes count towards the epoch their proposal's voting closes in, so totals are final once the epoch ends.
+#[account]
+#[derive(Default)]
+pub struct GovernanceRewardEpoch {
+    // Epoch index (timestamp / GOVERNANCE_REWARD_EPOCH_DURATION)
+    pub epoch: u64,
+    // Reward tokens shared among the epoch's voters, fixed when the epoch account is created
+    pub budget: u64,
+    // Sum of every voter's weight in the epoch, each capped at their largest single vote
+    pub total_weight: u64,
+    // Reward tokens claimed so far
+    pub claimed: u64,
+    // Most that passed-proposal rewards can pay out for proposals closing in the epoch, fixed at creation
+    pub proposer_reward_cap: u64,
+    // Passed-proposal rewards paid so far
+    pub proposer_rewards_paid: u64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl GovernanceRewardEpoch {
+    // Initialize an epoch with the current budgets
+    pub fn init(&mut self, epoch: u64, budget: u64, proposer_reward_cap: u64, bump: u8) {
+        self.epoch = epoch;
+        self.budget = budget;
+        self.total_weight = 0;
+        self.claimed = 0;
+        self.proposer_reward_cap = proposer_reward_cap;
+        self.proposer_rewards_paid = 0;
+        self.bump = bump;
+    }
+
+    // Timestamp at which the epoch ends and its rewards become claimable
+    pub fn end_time(&self) -> i64 {
+        (self.epoch as i64).saturating_add(1).saturating_mul(GOVERNANCE_REWARD_EPOCH_DURATION)
+    }
+
+    // Share of the budget earned by a voter whose capped weight in the epoch is `weight`
+    pub fn reward_for(&self, weight: u64) -> Result<u64> {
+        if self.total_weight == 0 {
+            return Ok(0);
+        }
+        let reward = (self.budget as u128) * (weight as u128) / (self.total_weight as u128);
+        u64::try_from(reward).map_err(|_| error!(SoreinError::ArithmeticError))
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // epoch (u64)
+        8 + // budget (u64)
+        8 + // total_weight (u64)
+        8 + // claimed (u64)
+        8 + // proposer_reward_cap (u64)
+        8 + // proposer_rewards_paid (u64)
+        1; // bump (u8)
+}
+
+// One voter's participation in a governance reward epoch (PDA seeded by epoch index and voter).
+// Voting on several proposals in an epoch doesn't multiply the voter's share: only their largest vote counts.
+#[account]
+#[derive(Default)]
+pub struct VoterRewardEpoch {
+    // Voter the participation belongs to
+    pub voter: Pubkey,
+    // Epoch index the participation counts towards
+    pub epoch: u64,
+    // Largest vote weight the voter cast on a proposal closing in the epoch
+    pub weight: u64,
+    // Whether the voter has claimed their share of the epoch's budget
+    pub reward_claimed: bool,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl VoterRewardEpoch {
+    // Initialize a voter's participation in an epoch
+    pub fn init(&mut self, voter: Pubkey, epoch: u64, bump: u8) {
+        self.voter = voter;
+        self.epoch = epoch;
+        self.weight = 0;
+        self.reward_claimed = false;
+        self.bump = bump;
+    }
+
+    // Raise the voter's weight to `weight` if it is larger, returning how much the epoch total grows by
+    pub fn raise_weight(&mut self, weight: u64) -> u64 {
+        let increase = weight.saturating_sub(self.weight);
+        self.weight = self.weight.max(weight);
+        increase
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // voter (Pubkey)
+        8 + // epoch (u64)
+        8 + // weight (u64)
+        1 + // reward_claimed (bool)
+        1; // bump (u8)
+}
+
+// Governance treasury (PDA seeded by "treasury"); owns one vault per mint
+#[account]
+#[derive(Default)]
+pub struct Treasury {
+    // Number of mints with a ledger and vault
+    pub ledger_count: u64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Treasury {
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // ledger_count (u64)
+        1; // bump (u8)
+}
+
+// Emergency council (PDA seeded by "council"); an M-of-N member set that can veto or fast-track
+// queued proposals and pause the platform until its term ends
+#[account]
+#[derive(Default)]
+pub struct Council {
+    // Current council members
+    pub members: Vec<Pubkey>,
+    // Number of member signatures required to act
+    pub threshold: u8,
+    // Timestamp after which the council can no longer act until it is rotated
+    pub term_end: i64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Council {
+    // Replace the member set and start a new term
+    pub fn rotate(&mut self, members: &[Pubkey], threshold: u8, now: i64) -> Result<()> {
+        require!(Self::is_valid_member_set(members, threshold), SoreinError::InvalidCouncil);
+        self.members = members.to_vec();
+        self.threshold = threshold;
+        self.term_end = now.checked_add(COUNCIL_TERM_DURATION).ok_or(SoreinError::InvalidCouncil)?;
+        Ok(())
+    }
+
+    // Whether the members are distinct, within the allowed council size and the threshold is reachable
+    pub fn is_valid_member_set(members: &[Pubkey], threshold: u8) -> bool {
+        if members.len() < MIN_COUNCIL_MEMBERS || members.len() > MAX_COUNCIL_MEMBERS {
+            return false;
+        }
+        if threshold == 0 || threshold as usize > members.len() {
+            return false;
+        }
+        members.iter().enumerate().all(|(index, member)| {
+            *member != Pubkey::default() && !members[..index].contains(member)
+        })
+    }
+
+    // Whether the key belongs to a current member
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.members.contains(key)
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        4 + 32 * MAX_COUNCIL_MEMBERS + // members (Vec with max length)
+        1 + // threshold (u8)
+        8 + // term_end (i64)
+        1; // bump (u8)
+}
+
+// Allocation buckets treasury spending is drawn from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum TreasuryBucket {
+    #[default]
+    ProposalFunding,
+    CommunityGrants,
+    Reserve,
+}
+
+impl TreasuryBucket {
+    pub const COUNT: usize = 3;
+
+    // Slot of this bucket in TreasuryLedger::budgets
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+// Spending cap and usage for one bucket in the current budget epoch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BucketBudget {
+    // Maximum amount the bucket can pay out per epoch (0 = no spending)
+    pub epoch_cap: u64,
+    // Amount paid out in the current epoch
+    pub spent: u64,
+}
+
+impl BucketBudget {
+    pub const SPACE: usize = 8 + // epoch_cap (u64)
+        8; // spent (u64)
+}
+
+// Per-mint treasury accounting (PDA seeded by mint)
+#[account]
+#[derive(Default)]
+pub struct TreasuryLedger {
+    // Mint tracked by this ledger
+    pub mint: Pubkey,
+    // Treasury-owned token account holding the mint
+    pub vault: Pubkey,
+    // Budgets indexed by TreasuryBucket
+    pub budgets: [BucketBudget; TreasuryBucket::COUNT],
+    // Start of the current budget epoch
+    pub epoch_start: i64,
+    // Total amount paid out over the ledger's lifetime
+    pub total_spent: u64,
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl TreasuryLedger {
+    // Initialize a ledger with no spending allowed until budgets are set
+    pub fn init(&mut self, mint: Pubkey, vault: Pubkey, epoch_start: i64, bump: u8) {
+        self.mint = mint;
+        self.vault = vault;
+        self.budgets = [BucketBudget::default(); TreasuryBucket::COUNT];
+        self.epoch_start = epoch_start;
+        self.total_spent = 0;
+        self.bump = bump;
+    }
+
+    // Reset spent counters once the budget epoch has elapsed
+    pub fn roll_epoch(&mut self, now: i64) {
+        if now >= self.epoch_start.saturating_add(TREASURY_EPOCH_DURATION) {
+            let elapsed_epochs = (now - self.epoch_start) / TREASURY_EPOCH_DURATION;
+            self.epoch_start += elapsed_epochs * TREASURY_EPOCH_DURATION;
+            for budget in self.budgets.iter_mut() {
+                budget.spent = 0;
+            }
+        }
+    }
+
+    // Record a payout against a bucket, enforcing its per-epoch cap
+    pub fn record_spend(&mut self, bucket: TreasuryBucket, amount: u64, now: i64) -> Result<()> {
+        self.roll_epoch(now);
+        let budget = &mut self.budgets[bucket.index()];
+        let spent = budget.spent.checked_add(amount).ok_or(SoreinError::ArithmeticError)?;
+        if spent > budget.epoch_cap {
+            return err!(SoreinError::TreasuryBudgetExceeded);
+        }
+        budget.spent = spent;
+        self.total_spent = self.total_spent.checked_add(amount).ok_or(SoreinError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Calculate space required for the account
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // mint (Pubkey)
+        32 + // vault (Pubkey)
+        BucketBudget::SPACE * TreasuryBucket::COUNT + // budgets (fixed array)
+        8 + // epoch_start (i64)
+        8 + // total_spent (u64)
         1; // bump (u8)
 }
 
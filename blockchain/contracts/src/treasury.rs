@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    PlatformConfig, Proposal, ProposalAction, ProposalStatus, Treasury, TreasuryLedger,
+    MAX_TREASURY_SPEND_BPS,
+};
+use crate::events::{ProposalExecuted, TreasuryBudgetSet, TreasurySpent};
+use crate::error::SoreinError;
+use crate::governance::check_ready_for_completion;
+use crate::math;
+
+/// Context for creating the governance treasury.
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    /// The account paying for the treasury account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The treasury PDA that owns every treasury vault.
+    #[account(
+        init,
+        payer = payer,
+        space = Treasury::SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to create the treasury. Permissionless, since the treasury holds no configuration
+/// and funds can only leave it through executed proposals.
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.ledger_count = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized by {}", ctx.accounts.payer.key());
+    Ok(())
+}
+
+/// Context for adding a mint to the treasury.
+#[derive(Accounts)]
+pub struct CreateTreasuryLedger<'info> {
+    /// The account paying for the ledger and vault accounts.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The treasury that will own the vault.
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    /// The mint the treasury will hold.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// The ledger tracking bucket budgets for the mint.
+    #[account(
+        init,
+        payer = payer,
+        space = TreasuryLedger::SPACE,
+        seeds = [b"treasury-ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub ledger: Account<'info, TreasuryLedger>,
+    /// The treasury-owned vault for the mint. Anyone can fund the treasury by transferring into it.
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"treasury-vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// The token program owning the mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
+    /// The system program for account initialization.
+    pub system_program: Program<'info, System>,
+}
+
+/// Instruction to add a mint to the treasury. Every bucket starts with a zero cap,
+/// so nothing can be spent until a SetTreasuryBudget proposal is executed.
+pub fn create_treasury_ledger(ctx: Context<CreateTreasuryLedger>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.ledger.init(
+        ctx.accounts.mint.key(),
+        ctx.accounts.vault.key(),
+        clock.unix_timestamp,
+        ctx.bumps.ledger,
+    );
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.ledger_count = treasury.ledger_count.checked_add(1).ok_or(SoreinError::ArithmeticError)?;
+
+    msg!("Treasury ledger created for mint {}", ctx.accounts.mint.key());
+    Ok(())
+}
+
+/// Context for executing a queued SetTreasuryBudget proposal.
+#[derive(Accounts)]
+pub struct ExecuteTreasuryBudget<'info> {
+    /// The caller who executes the proposal (can be anyone since it's permissionless).
+    pub executor: Signer<'info>,
+    /// The platform configuration account holding the execution grace period.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal carrying the budget change.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The ledger whose bucket cap is updated.
+    #[account(mut, seeds = [b"treasury-ledger", ledger.mint.as_ref()], bump = ledger.bump)]
+    pub ledger: Account<'info, TreasuryLedger>,
+}
+
+/// Instruction to apply a queued SetTreasuryBudget proposal to the matching ledger.
+pub fn execute_treasury_budget(ctx: Context<ExecuteTreasuryBudget>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_ready_for_completion(proposal, &ctx.accounts.platform_config)?;
+
+    let action = proposal.action.ok_or(SoreinError::ProposalNotExecutable)?;
+    let (mint, bucket, epoch_cap) = match action {
+        ProposalAction::SetTreasuryBudget { mint, bucket, epoch_cap } => (mint, bucket, epoch_cap),
+        _ => return err!(SoreinError::ProposalNotExecutable),
+    };
+    let ledger = &mut ctx.accounts.ledger;
+    if ledger.mint != mint {
+        return err!(SoreinError::InvalidAccount);
+    }
+
+    let clock = Clock::get()?;
+    ledger.roll_epoch(clock.unix_timestamp);
+    ledger.budgets[bucket.index()].epoch_cap = epoch_cap;
+
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+
+    emit!(TreasuryBudgetSet {
+        proposal_id,
+        mint,
+        bucket,
+        epoch_cap,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(ProposalExecuted {
+        proposal_id,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+        action: Some(action),
+    });
+
+    Ok(())
+}
+
+/// Context for executing a queued TreasurySpend proposal.
+#[derive(Accounts)]
+pub struct ExecuteTreasurySpend<'info> {
+    /// The caller who executes the proposal (can be anyone since it's permissionless).
+    pub executor: Signer<'info>,
+    /// The platform configuration account holding the execution grace period.
+    #[account(seeds = [b"platform-config"], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+    /// The queued proposal carrying the spend.
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    /// The treasury PDA that signs the transfer.
+    #[account(seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    /// The ledger the spend is charged against.
+    #[account(
+        mut,
+        seeds = [b"treasury-ledger", mint.key().as_ref()],
+        bump = ledger.bump,
+        has_one = mint @ SoreinError::InvalidAccount,
+        has_one = vault @ SoreinError::InvalidAccount
+    )]
+    pub ledger: Account<'info, TreasuryLedger>,
+    /// The mint being paid out.
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// The treasury vault the payout is drawn from.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// The recipient token account named in the proposal.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+    /// The token program owning the mint (SPL Token or Token-2022).
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Instruction to pay out a queued TreasurySpend proposal. This is the only way funds leave the treasury:
+/// the spend is charged against its bucket's epoch cap and limited to a share of the vault balance.
+pub fn execute_treasury_spend(ctx: Context<ExecuteTreasurySpend>, proposal_id: u64) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    if proposal.id != proposal_id {
+        return err!(SoreinError::InvalidProposal);
+    }
+    check_ready_for_completion(proposal, &ctx.accounts.platform_config)?;
+
+    let action = proposal.action.ok_or(SoreinError::ProposalNotExecutable)?;
+    let (mint, bucket, recipient, amount) = match action {
+        ProposalAction::TreasurySpend { mint, bucket, recipient, amount } => (mint, bucket, recipient, amount),
+        _ => return err!(SoreinError::ProposalNotExecutable),
+    };
+    if ctx.accounts.mint.key() != mint || ctx.accounts.recipient.key() != recipient {
+        return err!(SoreinError::InvalidAccount);
+    }
+    // A single proposal can only draw a limited share of the vault.
+    let max_spend = (ctx.accounts.vault.amount as u128) * (MAX_TREASURY_SPEND_BPS as u128) / math::BPS_DENOMINATOR;
+    if amount == 0 || amount as u128 > max_spend {
+        return err!(SoreinError::TreasurySpendTooLarge);
+    }
+
+    let clock = Clock::get()?;
+    ctx.accounts.ledger.record_spend(bucket, amount, clock.unix_timestamp)?;
+
+    // Transfer from the treasury vault to the recipient, signed by the treasury PDA
+    let seeds = &[b"treasury".as_ref(), &[ctx.accounts.treasury.bump]];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.recipient.to_account_info(),
+        authority: ctx.accounts.treasury.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = ProposalStatus::Executed;
+    proposal.executed_at = clock.unix_timestamp;
+
+    emit!(TreasurySpent {
+        proposal_id,
+        mint,
+        bucket,
+        recipient,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(ProposalExecuted {
+        proposal_id,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+        action: Some(action),
+    });
+
+    Ok(())
+}
@@ -11,7 +11,7 @@ mod tests {
 
     // Import the program to test (adjust based on your program name).
     use Dibbie_ai::program::SoreinAi; 
-    use Dibbie_ai::state::{PlatformConfig, Proposal, UserStake};
+    use Dibbie_ai::state::{PlatformConfig, Proposal, ProposalStatus, UserStake};
     use Dibbie_ai::instructions::{initialize_platform, stake_tokens, create_proposal, cast_vote, finalize_proposal};
 
     // Define constants for testing.
@@ -265,7 +265,7 @@ fastify.listen({ port: Number(process.env.PORT) || 3000, host: "0.0.0.0" }).then
         assert_eq!(proposal_data.creator, creator.pubkey(), "Creator mismatch");
         assert_eq!(proposal_data.title, title, "Title mismatch");
         assert_eq!(proposal_data.options, options, "Options mismatch");
-        assert_eq!(proposal_data.status, 0, "Proposal status should be active");
+        assert_eq!(proposal_data.status, ProposalStatus::Active, "Proposal status should be active");
     }
 
     // Test module for governance: casting a vote.
@@ -422,7 +422,7 @@ fastify.listen({ port: Number(process.env.PORT) || 3000, host: "0.0.0.0" }).then
         let proposal_data = Proposal::try_deserialize(&mut proposal_account.data.as_ref())
             .expect("Failed to deserialize proposal");
 
-        // Verify the proposal status (should be 2 = Rejected since no votes were cast with weight).
-        assert_eq!(proposal_data.status, 2, "Proposal status should be rejected");
+        // Verify the proposal status (quorum is not met since no votes were cast with weight).
+        assert_eq!(proposal_data.status, ProposalStatus::QuorumNotMet, "Proposal status should be quorum not met");
     }
 }
@@ -0,0 +1,886 @@
+// Instruction-level checks run against the program in solana-program-test: unstake cooldown and
+// vault transfers, the voting snapshot slot, double-vote rejection, relinquishing a vote, the execution
+// timelock and window, delegation limits, the NFT accounts of agent registration and the performance
+// score sources.
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::{AccountInfo, Clock, Pubkey};
+    use anchor_lang::solana_program::entrypoint::ProgramResult;
+    use anchor_lang::solana_program::{ed25519_program, system_program, sysvar};
+    use anchor_lang::{AccountDeserialize, AnchorSerialize, InstructionData, ToAccountMetas};
+    use anchor_spl::token::spl_token;
+    use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+    use solana_sdk::account::Account;
+    use solana_sdk::instruction::{Instruction, InstructionError};
+    use solana_sdk::program_pack::Pack;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::system_instruction;
+    use solana_sdk::transaction::{Transaction, TransactionError};
+
+    use Eonium_ai::error::SoreinError;
+    use Eonium_ai::state::{
+        PlatformConfig, Proposal, ProposalAction, ProposalContent, ProposalStatus, ProposalType, ScoreReport, UserStake,
+        VoteType, DEFAULT_EXECUTION_DELAY, DEFAULT_EXECUTION_GRACE_PERIOD, DEFAULT_MIN_VOTING_DURATION, MAX_DELEGATORS,
+    };
+    use Eonium_ai::{accounts, instruction, ID};
+
+    const DAY: i64 = 86_400;
+    const MIN_STAKE: u64 = 100;
+    const UNSTAKE_COOLDOWN: i64 = 7 * DAY;
+    const USER_BALANCE: u64 = 1_000_000;
+
+    // The entrypoint borrows the accounts for the program's lifetime, so they are leaked for the test run
+    fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        let accounts = Box::leak(Box::new(accounts.to_vec()));
+        Eonium_ai::entry(program_id, accounts, data)
+    }
+
+    fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &ID).0
+    }
+
+    fn platform_config_pda() -> Pubkey {
+        pda(&[b"platform-config"])
+    }
+
+    fn agent_pda(agent_id: u64) -> Pubkey {
+        pda(&[b"ai-agent", &agent_id.to_le_bytes()])
+    }
+
+    fn user_stake_pda(user: &Pubkey) -> Pubkey {
+        pda(&[b"user-stake", user.as_ref()])
+    }
+
+    fn checkpoints_pda(user: &Pubkey) -> Pubkey {
+        pda(&[b"stake-checkpoints", user.as_ref()])
+    }
+
+    fn proposal_pda(proposal_id: u64) -> Pubkey {
+        pda(&[b"proposal", &proposal_id.to_le_bytes()])
+    }
+
+    fn custom_error(code: u32) -> TransactionError {
+        TransactionError::InstructionError(0, InstructionError::Custom(code))
+    }
+
+    fn sorein_error(error: SoreinError) -> TransactionError {
+        custom_error(error.into())
+    }
+
+    // A platform with one registered agent and a stake mint the test can mint from
+    struct Harness {
+        context: ProgramTestContext,
+        admin: Keypair,
+        stake_mint: Pubkey,
+        reward_mint: Pubkey,
+    }
+
+    impl Harness {
+        async fn new(extra_accounts: Vec<(Pubkey, Account)>) -> Self {
+            let mut program_test = ProgramTest::new("sorein_ai", ID, processor!(process));
+            for (address, account) in extra_accounts {
+                program_test.add_account(address, account);
+            }
+            let context = program_test.start_with_context().await;
+            let admin = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+            let mut harness = Harness {
+                context,
+                admin,
+                stake_mint: Pubkey::default(),
+                reward_mint: Pubkey::default(),
+            };
+            harness.stake_mint = harness.create_mint().await;
+            harness.reward_mint = harness.create_mint().await;
+
+            let stake_mint = harness.stake_mint;
+            let reward_mint = harness.reward_mint;
+            let initialize = Instruction {
+                program_id: ID,
+                accounts: accounts::InitializePlatform {
+                    platform_config: platform_config_pda(),
+                    stake_mint,
+                    reward_mint,
+                    staking_vault: pda(&[b"staking-vault", stake_mint.as_ref()]),
+                    reward_vault: pda(&[b"reward-vault", reward_mint.as_ref()]),
+                    admin: harness.admin.pubkey(),
+                    stake_token_program: spl_token::ID,
+                    reward_token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::InitializePlatform {
+                    reward_rate_bps: 0,
+                    min_stake_amount: MIN_STAKE,
+                    epoch_duration: DAY,
+                    unstake_cooldown: UNSTAKE_COOLDOWN,
+                }
+                .data(),
+            };
+            harness.send(&[initialize], &[]).await.unwrap();
+
+            let register = register_ix(&harness.admin.pubkey(), 0, None, None);
+            harness.send(&[register], &[]).await.unwrap();
+            harness
+        }
+
+        fn staking_vault(&self) -> Pubkey {
+            pda(&[b"staking-vault", self.stake_mint.as_ref()])
+        }
+
+        // Sends the instructions paid by the admin, on a fresh blockhash so retries aren't deduplicated
+        async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), TransactionError> {
+            let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+            let mut all_signers: Vec<&Keypair> = vec![&self.admin];
+            all_signers.extend_from_slice(signers);
+            let transaction =
+                Transaction::new_signed_with_payer(instructions, Some(&self.admin.pubkey()), &all_signers, blockhash);
+            self.context
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .map_err(|error: BanksClientError| error.unwrap())
+        }
+
+        async fn create_mint(&mut self) -> Pubkey {
+            let mint = Keypair::new();
+            let rent = self.context.banks_client.get_rent().await.unwrap();
+            let instructions = [
+                system_instruction::create_account(
+                    &self.admin.pubkey(),
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &self.admin.pubkey(), None, 6)
+                    .unwrap(),
+            ];
+            self.send(&instructions, &[&mint]).await.unwrap();
+            mint.pubkey()
+        }
+
+        // A funded user with a stake token account holding USER_BALANCE
+        async fn create_user(&mut self) -> (Keypair, Pubkey) {
+            let user = Keypair::new();
+            let token_account = Keypair::new();
+            let rent = self.context.banks_client.get_rent().await.unwrap();
+            let instructions = [
+                system_instruction::transfer(&self.admin.pubkey(), &user.pubkey(), 1_000_000_000),
+                system_instruction::create_account(
+                    &self.admin.pubkey(),
+                    &token_account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account3(
+                    &spl_token::ID,
+                    &token_account.pubkey(),
+                    &self.stake_mint,
+                    &user.pubkey(),
+                )
+                .unwrap(),
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &self.stake_mint,
+                    &token_account.pubkey(),
+                    &self.admin.pubkey(),
+                    &[],
+                    USER_BALANCE,
+                )
+                .unwrap(),
+            ];
+            self.send(&instructions, &[&token_account]).await.unwrap();
+            (user, token_account.pubkey())
+        }
+
+        async fn token_balance(&mut self, token_account: Pubkey) -> u64 {
+            let account = self.context.banks_client.get_account(token_account).await.unwrap().unwrap();
+            spl_token::state::Account::unpack(&account.data).unwrap().amount
+        }
+
+        async fn fetch<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+            let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+            T::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+
+        async fn set_clock(&mut self, unix_timestamp: i64) {
+            let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+            clock.unix_timestamp = unix_timestamp;
+            self.context.set_sysvar(&clock);
+        }
+
+        async fn advance_slot(&mut self) {
+            let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+            self.context.warp_to_slot(clock.slot + 1).unwrap();
+            self.set_clock(clock.unix_timestamp).await;
+        }
+
+        fn stake_ix(&self, user: &Keypair, token_account: Pubkey, amount: u64) -> Instruction {
+            let user_key = user.pubkey();
+            Instruction {
+                program_id: ID,
+                accounts: accounts::StakeOnAgent {
+                    platform_config: platform_config_pda(),
+                    ai_agent: agent_pda(0),
+                    user_stake: user_stake_pda(&user_key),
+                    stake_position: pda(&[b"stake-position", user_key.as_ref(), &0u64.to_le_bytes()]),
+                    stake_checkpoints: checkpoints_pda(&user_key),
+                    user: user_key,
+                    stake_mint: self.stake_mint,
+                    user_token_account: token_account,
+                    staking_vault: self.staking_vault(),
+                    token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::StakeOnAgent { agent_id: 0, amount }.data(),
+            }
+        }
+
+        fn withdraw_ix(&self, user: &Keypair, token_account: Pubkey) -> Instruction {
+            Instruction {
+                program_id: ID,
+                accounts: accounts::WithdrawUnlocked {
+                    platform_config: platform_config_pda(),
+                    user_stake: user_stake_pda(&user.pubkey()),
+                    user: user.pubkey(),
+                    stake_mint: self.stake_mint,
+                    user_token_account: token_account,
+                    staking_vault: self.staking_vault(),
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::WithdrawUnlocked {}.data(),
+            }
+        }
+
+        fn admin_config_accounts(&self) -> Vec<anchor_lang::prelude::AccountMeta> {
+            accounts::UpdatePlatformConfig {
+                platform_config: platform_config_pda(),
+                admin: self.admin.pubkey(),
+            }
+            .to_account_metas(None)
+        }
+
+        async fn stake(&mut self, user: &Keypair, token_account: Pubkey, amount: u64) {
+            let stake = self.stake_ix(user, token_account, amount);
+            self.send(&[stake], &[user]).await.unwrap();
+        }
+    }
+
+    // Unstaked tokens stay in the vault until the cooldown elapses, then move back to the user
+    #[tokio::test]
+    async fn test_unstake_cooldown_and_vault_transfers() {
+        let mut harness = Harness::new(vec![]).await;
+        let (user, token_account) = harness.create_user().await;
+        let staking_vault = harness.staking_vault();
+
+        harness.stake(&user, token_account, 1_000).await;
+        assert_eq!(harness.token_balance(token_account).await, USER_BALANCE - 1_000);
+        assert_eq!(harness.token_balance(staking_vault).await, 1_000);
+
+        let unstake = Instruction {
+            program_id: ID,
+            accounts: accounts::UnstakeFromAgent {
+                platform_config: platform_config_pda(),
+                ai_agent: agent_pda(0),
+                user_stake: user_stake_pda(&user.pubkey()),
+                stake_position: pda(&[b"stake-position", user.pubkey().as_ref(), &0u64.to_le_bytes()]),
+                stake_checkpoints: checkpoints_pda(&user.pubkey()),
+                user: user.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::UnstakeFromAgent { agent_id: 0, amount: 400 }.data(),
+        };
+        harness.send(&[unstake], &[&user]).await.unwrap();
+        let user_stake: UserStake = harness.fetch(user_stake_pda(&user.pubkey())).await;
+        assert_eq!(user_stake.staked_amount, 600);
+        assert_eq!(user_stake.pending_withdrawals.len(), 1);
+        let unlock_at = user_stake.pending_withdrawals[0].unlock_at;
+
+        // Still cooling down: nothing moves
+        let result = harness.send(&[harness.withdraw_ix(&user, token_account)], &[&user]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::NoUnlockedWithdrawals));
+        harness.set_clock(unlock_at - 1).await;
+        let result = harness.send(&[harness.withdraw_ix(&user, token_account)], &[&user]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::NoUnlockedWithdrawals));
+        assert_eq!(harness.token_balance(staking_vault).await, 1_000);
+
+        // Once the cooldown has elapsed the queued amount is paid out of the vault exactly once
+        harness.set_clock(unlock_at).await;
+        harness.send(&[harness.withdraw_ix(&user, token_account)], &[&user]).await.unwrap();
+        assert_eq!(harness.token_balance(token_account).await, USER_BALANCE - 600);
+        assert_eq!(harness.token_balance(staking_vault).await, 600);
+        let result = harness.send(&[harness.withdraw_ix(&user, token_account)], &[&user]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::NoUnlockedWithdrawals));
+    }
+
+    // A staker with an open SetMinStake proposal they can vote on
+    struct GovernanceFixture {
+        harness: Harness,
+        voter: Keypair,
+        proposal: Pubkey,
+        reward_epoch: u64,
+    }
+
+    impl GovernanceFixture {
+        async fn new() -> Self {
+            Self::with_stake_settled(true).await
+        }
+
+        // `stake_settled` moves to the next slot between staking and sign-off; without it the stake
+        // lands in the sign-off slot, after the proposal's snapshot
+        async fn with_stake_settled(stake_settled: bool) -> Self {
+            let mut harness = Harness::new(vec![]).await;
+            let (voter, token_account) = harness.create_user().await;
+            harness.stake(&voter, token_account, 1_000).await;
+            if stake_settled {
+                harness.advance_slot().await;
+            }
+
+            let enable = Instruction {
+                program_id: ID,
+                accounts: harness.admin_config_accounts(),
+                data: instruction::SetGovernanceEnabled { enabled: true }.data(),
+            };
+            harness.send(&[enable], &[]).await.unwrap();
+
+            let proposal = proposal_pda(0);
+            let create = Instruction {
+                program_id: ID,
+                accounts: accounts::CreateProposal {
+                    creator: voter.pubkey(),
+                    platform_config: platform_config_pda(),
+                    proposal,
+                    creator_stake: user_stake_pda(&voter.pubkey()),
+                    stake_mint: harness.stake_mint,
+                    creator_token_account: token_account,
+                    deposit_escrow: pda(&[b"proposal-deposit", proposal.as_ref()]),
+                    ballot_box: None,
+                    token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::CreateProposal {
+                    content: ProposalContent {
+                        title: "Raise the minimum stake".to_string(),
+                        description: "Raise the minimum stake to 500".to_string(),
+                        options: vec!["Approve".to_string(), "Reject".to_string()],
+                    },
+                    proposal_type: ProposalType::ParameterChange,
+                    vote_type: VoteType::SingleChoice,
+                    voting_duration: DEFAULT_MIN_VOTING_DURATION,
+                    action: Some(ProposalAction::SetMinStake { min_stake_amount: 500 }),
+                }
+                .data(),
+            };
+            let sign_off = Instruction {
+                program_id: ID,
+                accounts: accounts::SignOffProposal {
+                    creator: voter.pubkey(),
+                    platform_config: platform_config_pda(),
+                    proposal,
+                }
+                .to_account_metas(None),
+                data: instruction::SignOffProposal { proposal_id: 0 }.data(),
+            };
+            harness.send(&[create, sign_off], &[&voter]).await.unwrap();
+
+            let reward_epoch = harness.fetch::<Proposal>(proposal).await.reward_epoch();
+            GovernanceFixture { harness, voter, proposal, reward_epoch }
+        }
+
+        fn cast_vote_ix(&self, choices: Vec<u8>) -> Instruction {
+            let voter = self.voter.pubkey();
+            let epoch = self.reward_epoch.to_le_bytes();
+            Instruction {
+                program_id: ID,
+                accounts: accounts::CastVote {
+                    voter,
+                    platform_config: platform_config_pda(),
+                    proposal: self.proposal,
+                    user_stake: user_stake_pda(&voter),
+                    stake_checkpoints: Some(checkpoints_pda(&voter)),
+                    ballot_box: None,
+                    vote_record: pda(&[b"vote-record", self.proposal.as_ref(), voter.as_ref()]),
+                    reward_epoch: pda(&[b"gov-reward-epoch", &epoch]),
+                    voter_reward_epoch: pda(&[b"voter-reward-epoch", &epoch, voter.as_ref()]),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::CastVote { proposal_id: 0, choices }.data(),
+            }
+        }
+
+        async fn cast_vote(&mut self, choices: Vec<u8>) -> Result<(), TransactionError> {
+            let cast_vote = self.cast_vote_ix(choices);
+            let voter = Keypair::from_bytes(&self.voter.to_bytes()).unwrap();
+            self.harness.send(&[cast_vote], &[&voter]).await
+        }
+
+        fn relinquish_vote_ix(&self) -> Instruction {
+            let voter = self.voter.pubkey();
+            let epoch = self.reward_epoch.to_le_bytes();
+            Instruction {
+                program_id: ID,
+                accounts: accounts::RelinquishVote {
+                    voter,
+                    proposal: self.proposal,
+                    ballot_box: None,
+                    vote_record: pda(&[b"vote-record", self.proposal.as_ref(), voter.as_ref()]),
+                    voter_reward_epoch: pda(&[b"voter-reward-epoch", &epoch, voter.as_ref()]),
+                }
+                .to_account_metas(None),
+                data: instruction::RelinquishVote { proposal_id: 0 }.data(),
+            }
+        }
+    }
+
+    // A second cast_vote from the same voter is rejected, whatever option it picks
+    #[tokio::test]
+    async fn test_vote_record_rejects_double_vote() {
+        let mut fixture = GovernanceFixture::new().await;
+        fixture.cast_vote(vec![0]).await.unwrap();
+
+        assert_eq!(fixture.cast_vote(vec![0]).await.unwrap_err(), sorein_error(SoreinError::AlreadyVoted));
+        assert_eq!(fixture.cast_vote(vec![1]).await.unwrap_err(), sorein_error(SoreinError::AlreadyVoted));
+
+        let proposal: Proposal = fixture.harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.votes, vec![1_000, 0]);
+        assert_eq!(proposal.voter_count, 1);
+    }
+
+    // An open vote that sets the voter's reward epoch weight can't be relinquished, so the weight can't be
+    // kept without the vote counting; once voting has closed the record can be
+    #[tokio::test]
+    async fn test_relinquish_keeps_reward_weight_backed_by_vote() {
+        let mut fixture = GovernanceFixture::new().await;
+        fixture.cast_vote(vec![0]).await.unwrap();
+        let voter = Keypair::from_bytes(&fixture.voter.to_bytes()).unwrap();
+
+        let result = fixture.harness.send(&[fixture.relinquish_vote_ix()], &[&voter]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::VoteHoldsRewardWeight));
+        let proposal: Proposal = fixture.harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.votes, vec![1_000, 0]);
+        assert_eq!(proposal.voter_count, 1);
+
+        fixture.harness.set_clock(proposal.end_time + 1).await;
+        fixture.harness.send(&[fixture.relinquish_vote_ix()], &[&voter]).await.unwrap();
+        let proposal: Proposal = fixture.harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.votes, vec![1_000, 0]);
+        assert_eq!(proposal.voter_count, 0);
+    }
+
+    // Stake that lands in the sign-off slot is after the snapshot and carries no voting power
+    #[tokio::test]
+    async fn test_stake_in_sign_off_slot_has_no_voting_power() {
+        let mut fixture = GovernanceFixture::with_stake_settled(false).await;
+        let proposal: Proposal = fixture.harness.fetch(fixture.proposal).await;
+        let clock: Clock = fixture.harness.context.banks_client.get_sysvar().await.unwrap();
+        assert_eq!(proposal.snapshot_slot, clock.slot - 1);
+
+        assert_eq!(fixture.cast_vote(vec![0]).await.unwrap_err(), sorein_error(SoreinError::InsufficientVotingPower));
+    }
+
+    fn finalize_ix(caller: &Pubkey, proposal: Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::FinalizeProposal {
+                caller: *caller,
+                platform_config: platform_config_pda(),
+                proposal,
+                ballot_box: None,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::FinalizeProposal { proposal_id: 0 }.data(),
+        }
+    }
+
+    fn execute_ix(executor: &Pubkey, proposal: Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::ExecuteProposal {
+                executor: *executor,
+                platform_config: platform_config_pda(),
+                proposal,
+            }
+            .to_account_metas(None),
+            data: instruction::ExecuteProposal { proposal_id: 0 }.data(),
+        }
+    }
+
+    // A passed proposal can't be executed until its timelock has elapsed
+    #[tokio::test]
+    async fn test_execute_blocked_by_timelock() {
+        let mut fixture = GovernanceFixture::new().await;
+        fixture.cast_vote(vec![0]).await.unwrap();
+
+        let harness = &mut fixture.harness;
+        let proposal: Proposal = harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.end_time, proposal.start_time + DEFAULT_MIN_VOTING_DURATION as i64);
+        harness.set_clock(proposal.end_time + 1).await;
+        let admin = harness.admin.pubkey();
+        harness.send(&[finalize_ix(&admin, fixture.proposal)], &[]).await.unwrap();
+        let proposal: Proposal = harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.status, ProposalStatus::Queued);
+        assert!(proposal.eta >= proposal.end_time + DEFAULT_EXECUTION_DELAY);
+
+        let execute = || execute_ix(&admin, fixture.proposal);
+        let result = harness.send(&[execute()], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::TimelockNotElapsed));
+        harness.set_clock(proposal.eta - 1).await;
+        let result = harness.send(&[execute()], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::TimelockNotElapsed));
+        let platform_config: PlatformConfig = harness.fetch(platform_config_pda()).await;
+        assert_eq!(platform_config.min_stake_amount, MIN_STAKE);
+
+        harness.set_clock(proposal.eta).await;
+        harness.send(&[execute()], &[]).await.unwrap();
+        let platform_config: PlatformConfig = harness.fetch(platform_config_pda()).await;
+        assert_eq!(platform_config.min_stake_amount, 500);
+        let proposal: Proposal = harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+    }
+
+    // A queued proposal that misses its execution window is finalized as ExecutionExpired, which can be
+    // closed, refunds the deposit and keeps its voting rewards
+    #[tokio::test]
+    async fn test_queued_proposal_expires_after_grace_period() {
+        let mut fixture = GovernanceFixture::new().await;
+        fixture.cast_vote(vec![0]).await.unwrap();
+
+        let harness = &mut fixture.harness;
+        let admin = harness.admin.pubkey();
+        let proposal: Proposal = harness.fetch(fixture.proposal).await;
+        harness.set_clock(proposal.end_time + 1).await;
+        harness.send(&[finalize_ix(&admin, fixture.proposal)], &[]).await.unwrap();
+        let proposal: Proposal = harness.fetch(fixture.proposal).await;
+        let expires_at = proposal.eta + DEFAULT_EXECUTION_GRACE_PERIOD;
+
+        // Still inside the execution window
+        harness.set_clock(expires_at).await;
+        let result = harness.send(&[finalize_ix(&admin, fixture.proposal)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::ProposalAlreadyFinalized));
+
+        harness.set_clock(expires_at + 1).await;
+        let result = harness.send(&[execute_ix(&admin, fixture.proposal)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::ExecutionWindowExpired));
+        harness.send(&[finalize_ix(&admin, fixture.proposal)], &[]).await.unwrap();
+        let proposal: Proposal = harness.fetch(fixture.proposal).await;
+        assert_eq!(proposal.status, ProposalStatus::ExecutionExpired);
+        assert_eq!(proposal.winning_option, Some(0));
+        assert!(proposal.is_closable());
+        assert!(proposal.deposit_refundable());
+        assert!(proposal.is_reward_eligible());
+        let result = harness.send(&[execute_ix(&admin, fixture.proposal)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::ProposalNotExecutable));
+    }
+
+    fn delegate_ix(delegator: &Pubkey, delegate: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::DelegateVotes {
+                delegator: *delegator,
+                platform_config: platform_config_pda(),
+                delegator_stake: user_stake_pda(delegator),
+                delegate: *delegate,
+                delegate_stake: user_stake_pda(delegate),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::DelegateVotes {}.data(),
+        }
+    }
+
+    // Delegation is single-hop, needs the minimum stake and is capped at MAX_DELEGATORS per delegate
+    #[tokio::test]
+    async fn test_delegation_limits() {
+        let mut harness = Harness::new(vec![]).await;
+        let delegate = Keypair::new();
+        let mut delegators = Vec::new();
+        for _ in 0..=MAX_DELEGATORS {
+            let (delegator, token_account) = harness.create_user().await;
+            harness.stake(&delegator, token_account, MIN_STAKE).await;
+            delegators.push(delegator);
+        }
+
+        // No self-delegation
+        let first = &delegators[0];
+        let result = harness.send(&[delegate_ix(&first.pubkey(), &first.pubkey())], &[first]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidDelegate));
+
+        // A user who never staked has nothing to delegate
+        let (unstaked, _) = harness.create_user().await;
+        let result = harness.send(&[delegate_ix(&unstaked.pubkey(), &delegate.pubkey())], &[&unstaked, &delegate]).await;
+        assert_eq!(result.unwrap_err(), custom_error(anchor_lang::error::ErrorCode::AccountNotInitialized.into()));
+
+        for delegator in &delegators[..MAX_DELEGATORS as usize] {
+            harness.send(&[delegate_ix(&delegator.pubkey(), &delegate.pubkey())], &[delegator, &delegate]).await.unwrap();
+        }
+        let delegate_stake: UserStake = harness.fetch(user_stake_pda(&delegate.pubkey())).await;
+        assert_eq!(delegate_stake.delegator_count, MAX_DELEGATORS);
+
+        // The delegate's slots are full
+        let last = &delegators[MAX_DELEGATORS as usize];
+        let result = harness.send(&[delegate_ix(&last.pubkey(), &delegate.pubkey())], &[last, &delegate]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::TooManyDelegators));
+
+        // Delegating twice, re-delegating received power and delegating to a delegator are all rejected
+        let second = &delegators[1];
+        let result = harness.send(&[delegate_ix(&first.pubkey(), &last.pubkey())], &[first, last]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::AlreadyDelegated));
+        let result = harness.send(&[delegate_ix(&delegate.pubkey(), &last.pubkey())], &[&delegate, last]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidDelegate));
+        let result = harness.send(&[delegate_ix(&last.pubkey(), &second.pubkey())], &[last, second]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidDelegate));
+    }
+
+    // Stake below the platform minimum can't be delegated
+    #[tokio::test]
+    async fn test_delegation_requires_min_stake() {
+        let mut harness = Harness::new(vec![]).await;
+        let (delegator, token_account) = harness.create_user().await;
+        let delegate = Keypair::new();
+        harness.stake(&delegator, token_account, MIN_STAKE).await;
+
+        let raise_min_stake = Instruction {
+            program_id: ID,
+            accounts: harness.admin_config_accounts(),
+            data: instruction::UpdatePlatformConfig {
+                reward_rate_bps: 0,
+                min_stake_amount: MIN_STAKE + 1,
+                epoch_duration: DAY,
+                unstake_cooldown: UNSTAKE_COOLDOWN,
+            }
+            .data(),
+        };
+        harness.send(&[raise_min_stake], &[]).await.unwrap();
+
+        let result = harness.send(&[delegate_ix(&delegator.pubkey(), &delegate.pubkey())], &[&delegator, &delegate]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InsufficientDelegatedStake));
+    }
+
+    fn register_ix(owner: &Pubkey, agent_id: u64, nft_mint: Option<Pubkey>, uri: Option<String>) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::RegisterAiAgent {
+                platform_config: platform_config_pda(),
+                ai_agent: agent_pda(agent_id),
+                owner_agents: pda(&[b"owner-agents", owner.as_ref()]),
+                owner: *owner,
+                system_program: system_program::ID,
+                nft_mint,
+                owner_nft_account: None,
+                metadata: None,
+                master_edition: None,
+                token_program: nft_mint.map(|_| spl_token::ID),
+                associated_token_program: None,
+                token_metadata_program: None,
+            }
+            .to_account_metas(None),
+            data: instruction::RegisterAiAgent {
+                name: "agent".to_string(),
+                description: "test agent".to_string(),
+                uri,
+            }
+            .data(),
+        }
+    }
+
+    // Registration mints the agent NFT only with a URI and the full set of NFT accounts
+    #[tokio::test]
+    async fn test_register_rejects_partial_nft_accounts() {
+        let mut harness = Harness::new(vec![]).await;
+        let admin = harness.admin.pubkey();
+        let nft_mint = pda(&[b"agent-mint", &1u64.to_le_bytes()]);
+
+        let result = harness.send(&[register_ix(&admin, 1, None, Some("https://agent".to_string()))], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidAccount));
+        let result = harness.send(&[register_ix(&admin, 1, Some(nft_mint), None)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidAccount));
+
+        harness.send(&[register_ix(&admin, 1, None, None)], &[]).await.unwrap();
+        let platform_config: PlatformConfig = harness.fetch(platform_config_pda()).await;
+        assert_eq!(platform_config.agent_count, 2);
+    }
+
+    const FEED_PROGRAM: Pubkey = Pubkey::new_from_array([7; 32]);
+
+    // An ai_data_feed account carrying `report`, owned by `owner`
+    fn feed_account(report: &ScoreReport, owner: Pubkey) -> Account {
+        let mut data = anchor_lang::solana_program::hash::hash(b"account:AiDataFeedData").to_bytes()[..8].to_vec();
+        Pubkey::default().serialize(&mut data).unwrap();
+        Pubkey::default().serialize(&mut data).unwrap();
+        "scores".to_string().serialize(&mut data).unwrap();
+        String::new().serialize(&mut data).unwrap();
+        report.try_to_vec().unwrap().serialize(&mut data).unwrap();
+        0i64.serialize(&mut data).unwrap();
+        true.serialize(&mut data).unwrap();
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    // An ed25519 program instruction carrying `signer`'s signature over `message`, laid out as the
+    // ed25519 program expects with the key, signature and message all inside the instruction
+    fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+        const DATA_START: u16 = 16;
+        let public_key_offset = DATA_START;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.pubkey().as_ref());
+        data.extend_from_slice(signer.sign_message(message).as_ref());
+        data.extend_from_slice(message);
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn score_ix(payer: &Pubkey, score_feed: Option<Pubkey>, evaluator: Option<Pubkey>) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::UpdatePerformanceScore {
+                payer: *payer,
+                platform_config: platform_config_pda(),
+                ai_agent: agent_pda(0),
+                performance: pda(&[b"agent-performance", &0u64.to_le_bytes()]),
+                score_feed,
+                evaluator: evaluator.map(|key| pda(&[b"evaluator", key.as_ref()])),
+                instructions_sysvar: evaluator.map(|_| sysvar::instructions::ID),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::UpdatePerformanceScore { agent_id: 0 }.data(),
+        }
+    }
+
+    fn set_score_feed_ix(admin: &Pubkey, score_feed: Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::SetScoreFeed {
+                admin: *admin,
+                platform_config: platform_config_pda(),
+            }
+            .to_account_metas(None),
+            data: instruction::SetScoreFeed { score_feed, score_feed_program: FEED_PROGRAM }.data(),
+        }
+    }
+
+    fn register_evaluator_ix(admin: &Pubkey, key: Pubkey) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::RegisterEvaluator {
+                admin: *admin,
+                platform_config: platform_config_pda(),
+                evaluator: pda(&[b"evaluator", key.as_ref()]),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::RegisterEvaluator { key }.data(),
+        }
+    }
+
+    // Feed reports are only taken from the configured feed account, owned by the configured feed program
+    #[tokio::test]
+    async fn test_score_feed_rejection_paths() {
+        let report = ScoreReport { agent_id: 0, score: 8_000, observed_at: 1 };
+        let feed = Pubkey::new_unique();
+        let foreign_feed = Pubkey::new_unique();
+        let mut harness = Harness::new(vec![
+            (feed, feed_account(&report, FEED_PROGRAM)),
+            (foreign_feed, feed_account(&report, Pubkey::new_unique())),
+        ])
+        .await;
+        let admin = harness.admin.pubkey();
+
+        // No feed configured yet
+        let result = harness.send(&[score_ix(&admin, Some(feed), None)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::UnauthorizedScoreSource));
+
+        // A look-alike account owned by another program
+        harness.send(&[set_score_feed_ix(&admin, foreign_feed)], &[]).await.unwrap();
+        let result = harness.send(&[score_ix(&admin, Some(foreign_feed), None)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidAccount));
+
+        // An account other than the configured feed
+        harness.send(&[set_score_feed_ix(&admin, feed)], &[]).await.unwrap();
+        let result = harness.send(&[score_ix(&admin, Some(foreign_feed), None)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::UnauthorizedScoreSource));
+
+        harness.send(&[score_ix(&admin, Some(feed), None)], &[]).await.unwrap();
+        let agent: Eonium_ai::state::AiAgent = harness.fetch(agent_pda(0)).await;
+        assert_eq!(agent.performance_score, 8_000);
+    }
+
+    // Attested reports need the ed25519 instruction right before, signed by the registered evaluator,
+    // and a report can't come from the feed and an attestation at once
+    #[tokio::test]
+    async fn test_score_attestation_rejection_paths() {
+        let report = ScoreReport { agent_id: 0, score: 6_000, observed_at: 1 };
+        let feed = Pubkey::new_unique();
+        let mut harness = Harness::new(vec![(feed, feed_account(&report, FEED_PROGRAM))]).await;
+        let admin = harness.admin.pubkey();
+        let evaluator = Keypair::new();
+        let impostor = Keypair::new();
+        harness
+            .send(&[set_score_feed_ix(&admin, feed), register_evaluator_ix(&admin, evaluator.pubkey())], &[])
+            .await
+            .unwrap();
+        let message = report.try_to_vec().unwrap();
+
+        // Missing ed25519 instruction
+        let result = harness.send(&[score_ix(&admin, None, Some(evaluator.pubkey()))], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::InvalidAttestation));
+
+        // Signed by a key other than the evaluator's
+        let result = harness
+            .send(&[ed25519_ix(&impostor, &message), score_ix(&admin, None, Some(evaluator.pubkey()))], &[])
+            .await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(1, InstructionError::Custom(SoreinError::UnauthorizedScoreSource.into()))
+        );
+
+        // Both sources at once
+        let result = harness
+            .send(&[ed25519_ix(&evaluator, &message), score_ix(&admin, Some(feed), Some(evaluator.pubkey()))], &[])
+            .await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(1, InstructionError::Custom(SoreinError::UnauthorizedScoreSource.into()))
+        );
+
+        // Neither source
+        let result = harness.send(&[score_ix(&admin, None, None)], &[]).await;
+        assert_eq!(result.unwrap_err(), sorein_error(SoreinError::UnauthorizedScoreSource));
+
+        harness
+            .send(&[ed25519_ix(&evaluator, &message), score_ix(&admin, None, Some(evaluator.pubkey()))], &[])
+            .await
+            .unwrap();
+        let agent: Eonium_ai::state::AiAgent = harness.fetch(agent_pda(0)).await;
+        assert_eq!(agent.performance_score, 6_000);
+    }
+}
@@ -0,0 +1,46 @@
+// The moving average that folds score reports into an agent's performance score.
+// Scores are in basis points, as produced from the evaluate.py metrics.
+
+#[cfg(test)]
+mod tests {
+    use Eonium_ai::math::ema;
+
+    // Constants mirroring the on-chain score settings
+    const ALPHA_BPS: u64 = 2_000;
+    const MAX_SCORE: u64 = 10_000;
+
+    // Each sample moves the average by alpha of the gap
+    #[test]
+    fn test_ema_step() {
+        assert_eq!(ema(5_000, 10_000, ALPHA_BPS), Some(6_000));
+        assert_eq!(ema(5_000, 0, ALPHA_BPS), Some(4_000));
+        assert_eq!(ema(7_000, 7_000, ALPHA_BPS), Some(7_000));
+    }
+
+    // Alpha bounds; 0% ignores the sample, 100% replaces the average, above 100% is rejected
+    #[test]
+    fn test_ema_alpha_bounds() {
+        assert_eq!(ema(5_000, 9_000, 0), Some(5_000));
+        assert_eq!(ema(5_000, 9_000, 10_000), Some(9_000));
+        assert_eq!(ema(5_000, 9_000, 10_001), None);
+    }
+
+    // Repeated samples converge on the sample and never leave the score range
+    #[test]
+    fn test_ema_converges_within_range() {
+        let mut average = 0;
+        for _ in 0..100 {
+            average = ema(average, MAX_SCORE, ALPHA_BPS).unwrap();
+            assert!(average <= MAX_SCORE);
+        }
+        // Rounding down leaves the average just short of the sample
+        assert!(MAX_SCORE - average < 5);
+    }
+
+    // Large values do not overflow
+    #[test]
+    fn test_ema_large_values() {
+        assert_eq!(ema(u64::MAX, u64::MAX, ALPHA_BPS), Some(u64::MAX));
+        assert_eq!(ema(0, u64::MAX, 10_000), Some(u64::MAX));
+    }
+}
@@ -0,0 +1,84 @@
+// Threshold, tie-break and instant-runoff helpers used when a proposal is finalized.
+// Threshold values mirror the thresholds section of configs/governance_config.json.
+
+#[cfg(test)]
+mod tests {
+    use Eonium_ai::math::{instant_runoff, leading_option, meets_threshold, split_vote_weight};
+
+    // Constants for the ratified thresholds
+    const APPROVAL_BPS: u64 = 6_000;
+    const QUORUM_BPS: u64 = 1_000;
+    const VETO_BPS: u64 = 3_330;
+
+    // Quorum is measured against the total voting supply
+    #[test]
+    fn test_quorum_threshold() {
+        assert!(meets_threshold(100, 1_000, QUORUM_BPS));
+        assert!(!meets_threshold(99, 1_000, QUORUM_BPS));
+        assert!(!meets_threshold(0, 0, QUORUM_BPS));
+        assert!(meets_threshold(0, 0, 0));
+    }
+
+    // Approval and veto thresholds are inclusive
+    #[test]
+    fn test_approval_and_veto_thresholds() {
+        assert!(meets_threshold(600, 1_000, APPROVAL_BPS));
+        assert!(!meets_threshold(599, 1_000, APPROVAL_BPS));
+        assert!(meets_threshold(333, 1_000, VETO_BPS));
+        assert!(!meets_threshold(332, 1_000, VETO_BPS));
+    }
+
+    // Thresholds do not overflow on large tallies
+    #[test]
+    fn test_threshold_with_large_tallies() {
+        assert!(meets_threshold(u64::MAX, u64::MAX, 10_000));
+        assert!(!meets_threshold(u64::MAX / 2, u64::MAX, APPROVAL_BPS));
+    }
+
+    // The leading option must be strictly ahead
+    #[test]
+    fn test_leading_option_tie_break() {
+        assert_eq!(leading_option(&[10, 30, 20]), Some(1));
+        assert_eq!(leading_option(&[30, 30, 20]), None);
+        assert_eq!(leading_option(&[20, 10, 20, 30]), Some(3));
+        assert_eq!(leading_option(&[0, 0]), None);
+        assert_eq!(leading_option(&[]), None);
+    }
+
+    // Approval votes split their weight without losing any to rounding
+    #[test]
+    fn test_split_vote_weight() {
+        assert_eq!(split_vote_weight(100, 1), vec![100]);
+        assert_eq!(split_vote_weight(100, 3), vec![34, 33, 33]);
+        assert_eq!(split_vote_weight(2, 3), vec![2, 0, 0]);
+        assert!(split_vote_weight(100, 0).is_empty());
+    }
+
+    // A first-round majority wins outright
+    #[test]
+    fn test_instant_runoff_first_round_majority() {
+        let ballots: [(&[u8], u64); 3] = [(&[0, 1], 60), (&[1, 0], 30), (&[2], 10)];
+        assert_eq!(instant_runoff(3, &ballots), Some((0, 60, 100)));
+    }
+
+    // Eliminated options transfer to the next preference, and exhausted ballots drop out
+    #[test]
+    fn test_instant_runoff_transfers_preferences() {
+        // Round 1: A 40, B 35, C 25 -> C eliminated; C's ballots go 20 to B and 5 are exhausted
+        let ballots: [(&[u8], u64); 4] = [(&[0], 40), (&[1], 35), (&[2, 1], 20), (&[2], 5)];
+        assert_eq!(instant_runoff(3, &ballots), Some((1, 55, 95)));
+        // The plurality leader would have been A
+        assert_eq!(leading_option(&[40, 35, 25]), Some(0));
+    }
+
+    // Ties among the last two options are a defeat, and the higher index is eliminated first
+    #[test]
+    fn test_instant_runoff_ties() {
+        let final_tie: [(&[u8], u64); 2] = [(&[0], 50), (&[1], 50)];
+        assert_eq!(instant_runoff(2, &final_tie), None);
+        // B and C tie for last; C (higher index) is eliminated and its votes push A over half
+        let elimination_tie: [(&[u8], u64); 3] = [(&[0], 40), (&[1], 30), (&[2, 0], 30)];
+        assert_eq!(instant_runoff(3, &elimination_tie), Some((0, 70, 100)));
+        assert_eq!(instant_runoff(3, &[]), None);
+    }
+}
@@ -0,0 +1,100 @@
+// Lockup multipliers, the voting power they produce and the stake history it is read from.
+// Tier values mirror the lockup section of configs/governance_config.json.
+
+#[cfg(test)]
+mod tests {
+    use Eonium_ai::math::{
+        linear_lockup_multiplier_bps, tiered_lockup_multiplier_bps, voting_power, BASE_MULTIPLIER_BPS,
+    };
+    use Eonium_ai::state::{StakeCheckpoints, MAX_STAKE_CHECKPOINTS};
+
+    // Constants for the ratified lockup schedule
+    const DAY: i64 = 86_400;
+    const MAX_LOCKUP_PERIOD: i64 = 365 * DAY;
+    const MAX_MULTIPLIER_BPS: u64 = 30_000;
+    const TIERS: [(i64, u64); 4] = [
+        (7 * DAY, 10_000),
+        (30 * DAY, 15_000),
+        (90 * DAY, 20_000),
+        (365 * DAY, 30_000),
+    ];
+
+    // Unlocked stake votes at 1.0x in both modes
+    #[test]
+    fn test_unlocked_stake_has_base_multiplier() {
+        assert_eq!(linear_lockup_multiplier_bps(0, MAX_LOCKUP_PERIOD, MAX_MULTIPLIER_BPS), Some(BASE_MULTIPLIER_BPS));
+        assert_eq!(tiered_lockup_multiplier_bps(0, &TIERS), BASE_MULTIPLIER_BPS);
+        assert_eq!(voting_power(1_000, BASE_MULTIPLIER_BPS), Some(1_000));
+    }
+
+    // Tiered multiplier matches the ratified tiers and steps down as the lock runs out
+    #[test]
+    fn test_tiered_multiplier_decays_through_tiers() {
+        assert_eq!(tiered_lockup_multiplier_bps(365 * DAY, &TIERS), 30_000);
+        assert_eq!(tiered_lockup_multiplier_bps(200 * DAY, &TIERS), 20_000);
+        assert_eq!(tiered_lockup_multiplier_bps(90 * DAY, &TIERS), 20_000);
+        assert_eq!(tiered_lockup_multiplier_bps(45 * DAY, &TIERS), 15_000);
+        assert_eq!(tiered_lockup_multiplier_bps(7 * DAY, &TIERS), 10_000);
+        assert_eq!(tiered_lockup_multiplier_bps(DAY, &TIERS), BASE_MULTIPLIER_BPS);
+    }
+
+    // Linear multiplier scales with remaining time and is capped at the max period
+    #[test]
+    fn test_linear_multiplier_scales_with_remaining_time() {
+        assert_eq!(linear_lockup_multiplier_bps(MAX_LOCKUP_PERIOD, MAX_LOCKUP_PERIOD, MAX_MULTIPLIER_BPS), Some(30_000));
+        assert_eq!(linear_lockup_multiplier_bps(MAX_LOCKUP_PERIOD / 2, MAX_LOCKUP_PERIOD, MAX_MULTIPLIER_BPS), Some(20_000));
+        assert_eq!(linear_lockup_multiplier_bps(2 * MAX_LOCKUP_PERIOD, MAX_LOCKUP_PERIOD, MAX_MULTIPLIER_BPS), Some(30_000));
+    }
+
+    // Voting power never increases as a lock approaches expiry
+    #[test]
+    fn test_voting_power_is_monotonic_in_remaining_time() {
+        let amount = 1_000_000_000u64;
+        let mut previous_linear = u64::MAX;
+        let mut previous_tiered = u64::MAX;
+        for remaining in (0..=MAX_LOCKUP_PERIOD).rev().step_by(DAY as usize) {
+            let linear = voting_power(
+                amount,
+                linear_lockup_multiplier_bps(remaining, MAX_LOCKUP_PERIOD, MAX_MULTIPLIER_BPS).unwrap(),
+            )
+            .unwrap();
+            let tiered = voting_power(amount, tiered_lockup_multiplier_bps(remaining, &TIERS)).unwrap();
+            assert!(linear <= previous_linear, "linear power increased at {} seconds remaining", remaining);
+            assert!(tiered <= previous_tiered, "tiered power increased at {} seconds remaining", remaining);
+            assert!(linear >= amount && tiered >= amount);
+            previous_linear = linear;
+            previous_tiered = tiered;
+        }
+    }
+
+    // Voting power overflow is reported instead of wrapping
+    #[test]
+    fn test_voting_power_overflow() {
+        assert_eq!(voting_power(u64::MAX, MAX_MULTIPLIER_BPS), None);
+    }
+
+    // A full stake history merges its oldest checkpoints, so a snapshot older than the history still sees
+    // the lowest stake held since instead of none
+    #[test]
+    fn test_full_checkpoint_history_keeps_old_snapshots_conservative() {
+        let mut history = StakeCheckpoints::default();
+        history.record(10, 1_000, 100 * DAY);
+        for i in 0..40u64 {
+            let amount = if i == 5 { 800 } else { 2_000 + i };
+            history.record(20 + i, amount, 0);
+        }
+
+        assert_eq!(history.checkpoints.len(), MAX_STAKE_CHECKPOINTS);
+        assert_eq!(history.checkpoints[0].slot, 10);
+        assert_eq!(history.checkpoints[0].lockup_end, 0);
+        assert_eq!(history.stake_at(9), 0);
+        assert_eq!(history.stake_at(15), 800);
+        assert_eq!(history.stake_at(30), 2_010);
+        assert_eq!(history.stake_at(59), 2_039);
+
+        // Changes within one slot still collapse into a single checkpoint
+        history.record(59, 500, 0);
+        assert_eq!(history.checkpoints.len(), MAX_STAKE_CHECKPOINTS);
+        assert_eq!(history.stake_at(59), 500);
+    }
+}